@@ -0,0 +1,29 @@
+//! Compares decoding a blob-sized hex string with the `hex` crate and with this crate's SIMD
+//! decoder.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+/// The size of a Deneb blob.
+const BLOB_BYTES: usize = 131_072;
+
+fn bench_decode(c: &mut Criterion) {
+    let blob: Vec<u8> = (0..BLOB_BYTES).map(|i| (i * 31) as u8).collect();
+    let hex = serde_utils::hex::encode(&blob);
+    let json = serde_json::to_string(&hex).unwrap();
+
+    let mut group = c.benchmark_group("blob");
+    group.throughput(Throughput::Bytes(BLOB_BYTES as u64));
+    group.bench_function("hex_crate", |b| {
+        b.iter(|| hex::decode(&black_box(&hex)[2..]).unwrap())
+    });
+    group.bench_function("decode", |b| {
+        b.iter(|| serde_utils::hex::decode(black_box(&hex)).unwrap())
+    });
+    group.bench_function("hex_vec", |b| {
+        b.iter(|| serde_json::from_str::<serde_utils::HexBytes>(black_box(&json)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_decode);
+criterion_main!(benches);
@@ -0,0 +1,142 @@
+//! Compares serializing hex and quoted integers through this crate with the previous approach of
+//! building a temporary `String` for every value.
+
+use alloy_primitives::{Address, B256};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use serde::{Serialize, Serializer};
+
+const COUNT: usize = 100_000;
+
+/// The previous implementation, which allocates two `String`s per value.
+fn serialize_allocating<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut hex_string: String = "0x".to_string();
+    hex_string.push_str(&hex::encode(bytes));
+    serializer.serialize_str(&hex_string)
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct AllocatingRoot(#[serde(serialize_with = "serialize_allocating")] [u8; 32]);
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct AllocatingPubkey(#[serde(serialize_with = "serialize_allocating")] [u8; 48]);
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct AllocatingBytes(#[serde(serialize_with = "serialize_allocating")] Vec<u8>);
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct AllocatingQuoted(#[serde(serialize_with = "serialize_quoted_allocating")] u64);
+
+fn serialize_quoted_allocating<S>(value: &u64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&format!("{}", value))
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct Pubkey(#[serde(with = "serde_utils::fixed_bytes_hex")] [u8; 48]);
+
+fn to_json<T: Serialize>(value: &T, buf: &mut Vec<u8>) {
+    buf.clear();
+    serde_json::to_writer(&mut *buf, value).unwrap();
+}
+
+fn bench_serialize(c: &mut Criterion) {
+    let roots: Vec<B256> = (0..COUNT)
+        .map(|i| B256::left_padding_from(&i.to_be_bytes()))
+        .collect();
+    let pubkeys: Vec<[u8; 48]> = (0..COUNT).map(|i| [i as u8; 48]).collect();
+    let addresses: Vec<Address> = (0..COUNT).map(|i| Address::repeat_byte(i as u8)).collect();
+    let blobs: Vec<Vec<u8>> = (0..COUNT / 100).map(|i| vec![i as u8; 4096]).collect();
+    let balances: Vec<u64> = (0..COUNT as u64).map(|i| i * 1_000_000_007).collect();
+    let mut buf = Vec::new();
+
+    let mut group = c.benchmark_group("roots");
+    group.throughput(Throughput::Elements(COUNT as u64));
+    let allocating: Vec<_> = roots.iter().map(|root| AllocatingRoot(root.0)).collect();
+    group.bench_function("allocating", |b| {
+        b.iter(|| to_json(black_box(&allocating), &mut buf))
+    });
+    let wrapped: Vec<_> = roots
+        .iter()
+        .copied()
+        .map(serde_utils::HexB256::from)
+        .collect();
+    group.bench_function("b256_hex", |b| {
+        b.iter(|| to_json(black_box(&wrapped), &mut buf))
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("pubkeys");
+    group.throughput(Throughput::Elements(COUNT as u64));
+    let allocating: Vec<_> = pubkeys.iter().copied().map(AllocatingPubkey).collect();
+    group.bench_function("allocating", |b| {
+        b.iter(|| to_json(black_box(&allocating), &mut buf))
+    });
+    let wrapped: Vec<_> = pubkeys.iter().copied().map(Pubkey).collect();
+    group.bench_function("fixed_bytes_hex", |b| {
+        b.iter(|| to_json(black_box(&wrapped), &mut buf))
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("addresses");
+    group.throughput(Throughput::Elements(COUNT as u64));
+    let allocating: Vec<_> = addresses
+        .iter()
+        .map(|address| AllocatingBytes(address.to_vec()))
+        .collect();
+    group.bench_function("allocating", |b| {
+        b.iter(|| to_json(black_box(&allocating), &mut buf))
+    });
+    let wrapped: Vec<_> = addresses
+        .iter()
+        .copied()
+        .map(serde_utils::HexAddress::from)
+        .collect();
+    group.bench_function("address_hex", |b| {
+        b.iter(|| to_json(black_box(&wrapped), &mut buf))
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("blobs");
+    group.throughput(Throughput::Bytes((blobs.len() * 4096) as u64));
+    let allocating: Vec<_> = blobs.iter().cloned().map(AllocatingBytes).collect();
+    group.bench_function("allocating", |b| {
+        b.iter(|| to_json(black_box(&allocating), &mut buf))
+    });
+    let wrapped: Vec<_> = blobs
+        .iter()
+        .cloned()
+        .map(serde_utils::HexBytes::from)
+        .collect();
+    group.bench_function("hex_vec", |b| {
+        b.iter(|| to_json(black_box(&wrapped), &mut buf))
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("balances");
+    group.throughput(Throughput::Elements(COUNT as u64));
+    let allocating: Vec<_> = balances.iter().copied().map(AllocatingQuoted).collect();
+    group.bench_function("allocating", |b| {
+        b.iter(|| to_json(black_box(&allocating), &mut buf))
+    });
+    let wrapped: Vec<_> = balances
+        .iter()
+        .map(|&value| serde_utils::quoted_u64::Quoted { value })
+        .collect();
+    group.bench_function("quoted_u64", |b| {
+        b.iter(|| to_json(black_box(&wrapped), &mut buf))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_serialize);
+criterion_main!(benches);
@@ -1,42 +1,262 @@
 use alloy_primitives::Address;
+use core::ops::{Deref, DerefMut};
 use serde::de::Error;
 use serde::{Deserializer, Serializer};
+use serde_derive::{Deserialize, Serialize};
 
-use crate::hex::PrefixedHexVisitor;
+use crate::fixed_bytes_hex;
+use crate::hex::{decode_to_array, serialize_prefixed, HexError};
 
 pub fn serialize<S>(address: &Address, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    let mut hex_string: String = "0x".to_string();
-    hex_string.push_str(&hex::encode(&address));
+    if !serializer.is_human_readable() {
+        return serializer.serialize_bytes(address.as_slice());
+    }
 
-    serializer.serialize_str(&hex_string)
+    serialize_prefixed(address.as_slice(), serializer)
 }
 
 pub fn deserialize<'de, D>(deserializer: D) -> Result<Address, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let decoded = deserializer.deserialize_str(PrefixedHexVisitor)?;
+    fixed_bytes_hex::deserialize::<_, 20>(deserializer).map(Into::into)
+}
+
+/// The JSON schema of a 0x-prefixed hex string of 20 bytes.
+///
+/// Usage: `#[schemars(schema_with = "address_hex::schema")]`.
+#[cfg(feature = "schemars")]
+pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    crate::schema::fixed_hex_bytes(20)
+}
+
+/// The OpenAPI schema of a 0x-prefixed hex string of 20 bytes.
+///
+/// Usage: `#[schema(schema_with = address_hex::openapi_schema)]`.
+#[cfg(feature = "utoipa")]
+pub fn openapi_schema() -> utoipa::openapi::schema::Schema {
+    crate::openapi::address()
+}
+
+/// Composable wrapper type for formatting an `Address` as a 0x-prefixed hex string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct HexAddress {
+    #[serde(with = "self")]
+    pub value: Address,
+}
+
+impl Deref for HexAddress {
+    type Target = Address;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl DerefMut for HexAddress {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
 
-    if decoded.len() != 20 {
-        return Err(D::Error::custom(format!(
-            "expected {} bytes for array, got {}",
-            20,
-            decoded.len()
-        )));
+impl From<Address> for HexAddress {
+    fn from(value: Address) -> Self {
+        Self { value }
     }
+}
 
-    let mut array = [0; 20];
-    array.copy_from_slice(&decoded);
-    Ok(array.into())
+impl From<HexAddress> for Address {
+    fn from(wrapper: HexAddress) -> Self {
+        wrapper.value
+    }
+}
+
+#[cfg(feature = "schemars")]
+crate::schema::impl_json_schema!("HexAddress", [] HexAddress, crate::schema::fixed_hex_bytes(20));
+
+#[cfg(feature = "utoipa")]
+crate::openapi::impl_to_schema!("HexAddress", [] HexAddress, crate::openapi::address());
+
+/// Formats an `Address` as an EIP-55 mixed-case checksummed hex string.
+///
+/// Usage: `#[serde(with = "address_hex::checksummed")]`.
+///
+/// Deserialization accepts any casing, as per `address_hex::deserialize`.
+pub mod checksummed {
+    pub use super::deserialize;
+    use super::*;
+
+    pub fn serialize<S>(address: &Address, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return super::serialize(address, serializer);
+        }
+
+        serializer.serialize_str(address.to_checksum_buffer(None).as_str())
+    }
+
+    #[cfg(feature = "schemars")]
+    pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::schema::checksummed_address()
+    }
+
+    #[cfg(feature = "utoipa")]
+    pub fn openapi_schema() -> utoipa::openapi::schema::Schema {
+        crate::openapi::checksummed_address()
+    }
+}
+
+/// Formats an `Address` with an EIP-55 checksum, and validates the checksum when deserializing.
+///
+/// Usage: `#[serde(with = "address_hex::strict_checksum")]`.
+///
+/// All-lowercase and all-uppercase addresses carry no checksum and are accepted, but mixed-case
+/// addresses are rejected unless they match their EIP-55 checksum.
+///
+/// Binary formats store the raw bytes, which carry no checksum to validate.
+pub mod strict_checksum {
+    pub use super::checksummed::serialize;
+    use super::*;
+    use core::fmt;
+    use serde::de::Visitor;
+
+    pub struct ChecksumVisitor;
+
+    impl<'de> Visitor<'de> for ChecksumVisitor {
+        type Value = Address;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a 0x-prefixed address with a valid EIP-55 checksum")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            let address = Address::from(decode_to_array(value).map_err(E::custom)?);
+            let digits = &value[2..];
+            let has_lower = digits.bytes().any(|b| b.is_ascii_lowercase());
+            let has_upper = digits.bytes().any(|b| b.is_ascii_uppercase());
+
+            if has_lower && has_upper {
+                let expected = address.to_checksum_buffer(None);
+                if expected.as_str() != value {
+                    return Err(E::custom(HexError::InvalidChecksum {
+                        expected: expected.to_string(),
+                    }));
+                }
+            }
+
+            Ok(address)
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Address, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return super::deserialize(deserializer);
+        }
+
+        deserializer.deserialize_str(ChecksumVisitor)
+    }
+
+    #[cfg(feature = "schemars")]
+    pub use super::checksummed::schema;
+
+    #[cfg(feature = "utoipa")]
+    pub use super::checksummed::openapi_schema;
+}
+
+/// Wrapper type for formatting an `Address` with an EIP-55 checksum.
+///
+/// This is the composable equivalent of `address_hex::checksummed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct ChecksummedAddress {
+    #[serde(with = "checksummed")]
+    pub value: Address,
+}
+
+impl Deref for ChecksummedAddress {
+    type Target = Address;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl DerefMut for ChecksummedAddress {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl From<Address> for ChecksummedAddress {
+    fn from(value: Address) -> Self {
+        Self { value }
+    }
+}
+
+impl From<ChecksummedAddress> for Address {
+    fn from(wrapper: ChecksummedAddress) -> Self {
+        wrapper.value
+    }
+}
+
+#[cfg(feature = "schemars")]
+crate::schema::impl_json_schema!(
+    "ChecksummedAddress",
+    [] ChecksummedAddress,
+    crate::schema::checksummed_address()
+);
+
+#[cfg(feature = "utoipa")]
+crate::openapi::impl_to_schema!(
+    "ChecksummedAddress",
+    [] ChecksummedAddress,
+    crate::openapi::checksummed_address()
+);
+
+/// Formats `Option<Address>` as a 0x-prefixed hex string or `null`.
+///
+/// Usage: `#[serde(with = "address_hex::option", default)]`.
+pub mod option {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    pub fn serialize<S>(value: &Option<Address>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        value.map(HexAddress::from).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Address>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<HexAddress>::deserialize(deserializer)?.map(Address::from))
+    }
+
+    #[cfg(feature = "schemars")]
+    pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::schema::nullable(crate::schema::fixed_hex_bytes(20))
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use std::str::FromStr;
+    use core::str::FromStr;
 
+    use super::{ChecksummedAddress, HexAddress};
     use alloy_primitives::Address;
     use serde::{Deserialize, Serialize};
     use serde_json;
@@ -126,4 +346,148 @@ mod test {
         serde_json::from_str::<Wrapper>("\"0x-100000000000000000000000000000000000000\"")
             .unwrap_err();
     }
+
+    #[test]
+    fn wrapper_composes() {
+        let address = Address::repeat_byte(0xab);
+        let wrapper = HexAddress::from(address);
+        assert_eq!(*wrapper, address);
+
+        let value = Some(wrapper);
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, format!("\"0x{}\"", "ab".repeat(20)));
+        assert_eq!(
+            serde_json::from_str::<Option<HexAddress>>(&json).unwrap(),
+            value
+        );
+        assert_eq!(
+            serde_json::from_str::<Option<HexAddress>>("null").unwrap(),
+            None
+        );
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct ChecksummedWrapper {
+        #[serde(with = "super::checksummed")]
+        val: Address,
+    }
+
+    // Test vectors from EIP-55.
+    const CHECKSUMMED: [&str; 4] = [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ];
+
+    #[test]
+    fn checksummed_encoding() {
+        for expected in CHECKSUMMED {
+            let val = Address::from_str(expected).unwrap();
+            assert_eq!(
+                serde_json::to_string(&ChecksummedWrapper { val }).unwrap(),
+                format!("\"{}\"", expected)
+            );
+            assert_eq!(
+                serde_json::to_string(&ChecksummedAddress::from(val)).unwrap(),
+                format!("\"{}\"", expected)
+            );
+            // The default encoding is unchanged.
+            assert_eq!(
+                serde_json::to_string(&Wrapper { val }).unwrap(),
+                format!("\"{}\"", expected.to_lowercase())
+            );
+        }
+    }
+
+    #[test]
+    fn checksummed_decoding() {
+        for checksummed in CHECKSUMMED {
+            let val = Address::from_str(checksummed).unwrap();
+            for input in [checksummed.to_string(), checksummed.to_lowercase()] {
+                assert_eq!(
+                    serde_json::from_str::<ChecksummedWrapper>(&format!("\"{}\"", input)).unwrap(),
+                    ChecksummedWrapper { val }
+                );
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct StrictWrapper {
+        #[serde(with = "super::strict_checksum")]
+        val: Address,
+    }
+
+    #[test]
+    fn strict_checksum_decoding() {
+        for checksummed in CHECKSUMMED {
+            let val = Address::from_str(checksummed).unwrap();
+            let lower = checksummed.to_lowercase();
+            let upper = format!("0x{}", checksummed[2..].to_uppercase());
+            for input in [checksummed, &lower, &upper] {
+                assert_eq!(
+                    serde_json::from_str::<StrictWrapper>(&format!("\"{}\"", input)).unwrap(),
+                    StrictWrapper { val }
+                );
+            }
+            assert_eq!(
+                serde_json::to_string(&StrictWrapper { val }).unwrap(),
+                format!("\"{}\"", checksummed)
+            );
+        }
+
+        // Flip the case of a single letter.
+        let err =
+            serde_json::from_str::<StrictWrapper>("\"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD\"")
+                .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+
+        // Wrong length and missing prefix are still rejected.
+        serde_json::from_str::<StrictWrapper>("\"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA\"")
+            .unwrap_err();
+        serde_json::from_str::<StrictWrapper>("\"5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed\"")
+            .unwrap_err();
+    }
+
+    crate::test_utils::option_wrapper!(Address);
+
+    #[test]
+    fn option() {
+        crate::test_utils::check_option(
+            OptionWrapper {
+                val: Some(Address::repeat_byte(0xab)),
+            },
+            &format!(r#""0x{}""#, "ab".repeat(20)),
+            &[r#""0xab""#],
+        );
+    }
+
+    #[test]
+    fn binary() {
+        let val = Address::from_str(CHECKSUMMED[0]).unwrap();
+        let bytes = bincode::serialize(&Wrapper { val }).unwrap();
+        assert_eq!(bytes[..8], [20, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(bytes[8..], val[..]);
+        assert_eq!(
+            bincode::deserialize::<Wrapper>(&bytes).unwrap(),
+            Wrapper { val }
+        );
+
+        // The checksum only applies to the human-readable encoding.
+        assert_eq!(
+            bincode::serialize(&ChecksummedWrapper { val }).unwrap(),
+            bytes
+        );
+        assert_eq!(
+            bincode::deserialize::<StrictWrapper>(&bytes).unwrap(),
+            StrictWrapper { val }
+        );
+
+        bincode::deserialize::<Wrapper>(&[19, 0, 0, 0, 0, 0, 0, 0, 0]).unwrap_err();
+    }
 }
@@ -1,36 +1,111 @@
 use alloy_primitives::B256;
-use serde::de::Error;
+use core::ops::{Deref, DerefMut};
 use serde::{Deserializer, Serializer};
+use serde_derive::{Deserialize, Serialize};
 
-use crate::hex::PrefixedHexVisitor;
+use crate::fixed_bytes_hex;
+use crate::hex::serialize_prefixed;
 
 pub fn serialize<S>(hash: &B256, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    let mut hex_string: String = "0x".to_string();
-    hex_string.push_str(&hex::encode(&hash));
+    if !serializer.is_human_readable() {
+        return serializer.serialize_bytes(hash.as_slice());
+    }
 
-    serializer.serialize_str(&hex_string)
+    serialize_prefixed(hash.as_slice(), serializer)
 }
 
 pub fn deserialize<'de, D>(deserializer: D) -> Result<B256, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let decoded = deserializer.deserialize_str(PrefixedHexVisitor)?;
-
-    if decoded.len() != 32 {
-        return Err(D::Error::custom(format!(
-            "expected {} bytes for array, got {}",
-            32,
-            decoded.len()
-        )));
+    fixed_bytes_hex::deserialize::<_, 32>(deserializer).map(Into::into)
+}
+
+/// The JSON schema of a 0x-prefixed hex string of 32 bytes.
+///
+/// Usage: `#[schemars(schema_with = "b256_hex::schema")]`.
+#[cfg(feature = "schemars")]
+pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    crate::schema::fixed_hex_bytes(32)
+}
+
+/// The OpenAPI schema of a 0x-prefixed hex string of 32 bytes.
+///
+/// Usage: `#[schema(schema_with = b256_hex::openapi_schema)]`.
+#[cfg(feature = "utoipa")]
+pub fn openapi_schema() -> utoipa::openapi::schema::Schema {
+    crate::openapi::b256()
+}
+
+/// Composable wrapper type for formatting a `B256` as a 0x-prefixed hex string, e.g. inside a
+/// `Vec<HexB256>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct HexB256 {
+    #[serde(with = "self")]
+    pub value: B256,
+}
+
+impl Deref for HexB256 {
+    type Target = B256;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl DerefMut for HexB256 {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl From<B256> for HexB256 {
+    fn from(value: B256) -> Self {
+        Self { value }
+    }
+}
+
+impl From<HexB256> for B256 {
+    fn from(wrapper: HexB256) -> Self {
+        wrapper.value
+    }
+}
+
+#[cfg(feature = "schemars")]
+crate::schema::impl_json_schema!("HexB256", [] HexB256, crate::schema::fixed_hex_bytes(32));
+
+#[cfg(feature = "utoipa")]
+crate::openapi::impl_to_schema!("HexB256", [] HexB256, crate::openapi::b256());
+
+/// Formats `Option<B256>` as a 0x-prefixed hex string or `null`.
+///
+/// Usage: `#[serde(with = "b256_hex::option", default)]`.
+pub mod option {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    pub fn serialize<S>(value: &Option<B256>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        value.map(HexB256::from).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<B256>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<HexB256>::deserialize(deserializer)?.map(B256::from))
     }
 
-    let mut array = [0; 32];
-    array.copy_from_slice(&decoded);
-    Ok(array.into())
+    #[cfg(feature = "schemars")]
+    pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::schema::nullable(crate::schema::fixed_hex_bytes(32))
+    }
 }
 
 #[cfg(test)]
@@ -97,4 +172,47 @@ mod test {
         )
         .unwrap_err();
     }
+
+    #[test]
+    fn wrapper_composes() {
+        let values = vec![HexB256::from(B256::repeat_byte(0x03)), HexB256::default()];
+        let json = serde_json::to_string(&values).unwrap();
+        assert_eq!(
+            json,
+            format!("[\"0x{}\",\"0x{}\"]", "03".repeat(32), "00".repeat(32))
+        );
+        assert_eq!(serde_json::from_str::<Vec<HexB256>>(&json).unwrap(), values);
+        assert_eq!(B256::from(values[0]), B256::repeat_byte(0x03));
+
+        serde_json::from_str::<Vec<HexB256>>("[\"0x03\"]").unwrap_err();
+    }
+
+    crate::test_utils::option_wrapper!(B256);
+
+    #[test]
+    fn option() {
+        crate::test_utils::check_option(
+            OptionWrapper {
+                val: Some(B256::repeat_byte(0x03)),
+            },
+            &format!(r#""0x{}""#, "03".repeat(32)),
+            &[r#""0x03""#],
+        );
+    }
+
+    #[test]
+    fn binary() {
+        let wrapper = Wrapper {
+            val: B256::repeat_byte(0x03),
+        };
+        let bytes = bincode::serialize(&wrapper).unwrap();
+        assert_eq!(bytes.len(), 8 + 32);
+        assert_eq!(bincode::deserialize::<Wrapper>(&bytes).unwrap(), wrapper);
+
+        let some = OptionWrapper {
+            val: Some(B256::repeat_byte(0x03)),
+        };
+        let bytes = bincode::serialize(&some).unwrap();
+        assert_eq!(bincode::deserialize::<OptionWrapper>(&bytes).unwrap(), some);
+    }
 }
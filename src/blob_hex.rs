@@ -0,0 +1,183 @@
+//! Formats an EIP-4844 blob, `Box<[u8; BYTES_PER_BLOB]>`, as a 0x-prefixed hex string.
+//!
+//! Blobs are decoded directly into a single heap allocation, and must be exactly
+//! `BYTES_PER_BLOB` bytes long.
+//!
+//! Binary (non human-readable) formats store the raw bytes instead.
+
+use crate::fixed_bytes_hex::boxed;
+use alloc::boxed::Box;
+use core::ops::{Deref, DerefMut};
+use serde::{Deserializer, Serializer};
+use serde_derive::{Deserialize, Serialize};
+
+/// The number of bytes in a blob, as per EIP-4844.
+pub const BYTES_PER_BLOB: usize = 131_072;
+
+pub fn serialize<S>(blob: &[u8; BYTES_PER_BLOB], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    boxed::serialize(blob, serializer)
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Box<[u8; BYTES_PER_BLOB]>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    boxed::deserialize(deserializer)
+}
+
+/// The JSON schema of a 0x-prefixed hex string of `BYTES_PER_BLOB` bytes.
+///
+/// Usage: `#[schemars(schema_with = "blob_hex::schema")]`.
+#[cfg(feature = "schemars")]
+pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    crate::schema::fixed_hex_bytes(BYTES_PER_BLOB)
+}
+
+/// Composable wrapper type for formatting a blob as a 0x-prefixed hex string, e.g. inside a
+/// `Vec<HexBlob>`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct HexBlob {
+    #[serde(with = "self")]
+    pub value: Box<[u8; BYTES_PER_BLOB]>,
+}
+
+impl Deref for HexBlob {
+    type Target = [u8; BYTES_PER_BLOB];
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl DerefMut for HexBlob {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl From<Box<[u8; BYTES_PER_BLOB]>> for HexBlob {
+    fn from(value: Box<[u8; BYTES_PER_BLOB]>) -> Self {
+        Self { value }
+    }
+}
+
+impl From<HexBlob> for Box<[u8; BYTES_PER_BLOB]> {
+    fn from(wrapper: HexBlob) -> Self {
+        wrapper.value
+    }
+}
+
+#[cfg(feature = "schemars")]
+crate::schema::impl_json_schema!(
+    "HexBlob",
+    [] HexBlob,
+    crate::schema::fixed_hex_bytes(BYTES_PER_BLOB)
+);
+
+/// Formats `Option<Box<[u8; BYTES_PER_BLOB]>>` as a 0x-prefixed hex string or `null`.
+///
+/// Usage: `#[serde(with = "blob_hex::option", default)]`.
+pub mod option {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize)]
+    #[serde(transparent)]
+    struct BorrowedHexBlob<'a>(#[serde(with = "super")] &'a [u8; BYTES_PER_BLOB]);
+
+    pub fn serialize<S>(
+        value: &Option<Box<[u8; BYTES_PER_BLOB]>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        value.as_deref().map(BorrowedHexBlob).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<Option<Box<[u8; BYTES_PER_BLOB]>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<HexBlob>::deserialize(deserializer)?.map(|wrapper| wrapper.value))
+    }
+
+    #[cfg(feature = "schemars")]
+    pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::schema::nullable(crate::schema::fixed_hex_bytes(BYTES_PER_BLOB))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn blob() -> HexBlob {
+        let zeroed: Box<[u8; BYTES_PER_BLOB]> = vec![0; BYTES_PER_BLOB]
+            .into_boxed_slice()
+            .try_into()
+            .unwrap();
+        let mut blob = HexBlob::from(zeroed);
+        for (i, byte) in blob.iter_mut().enumerate() {
+            *byte = (i % 251) as u8;
+        }
+        blob
+    }
+
+    #[test]
+    fn round_trip() {
+        let blob = blob();
+        let json = serde_json::to_string(&blob).unwrap();
+        assert_eq!(json.len(), 2 + 2 + 2 * BYTES_PER_BLOB);
+        assert_eq!(json, format!("\"0x{}\"", hex::encode(&blob[..])));
+        assert_eq!(serde_json::from_str::<HexBlob>(&json).unwrap(), blob);
+    }
+
+    #[test]
+    fn wrong_length() {
+        let hex = hex::encode(&blob()[..]);
+
+        let truncated = format!("\"0x{}\"", &hex[..hex.len() - 2]);
+        let err = serde_json::from_str::<HexBlob>(&truncated).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("expected 131072 bytes, got 131071"));
+
+        let oversized = format!("\"0x{}00\"", hex);
+        let err = serde_json::from_str::<HexBlob>(&oversized).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("expected 131072 bytes, got 131073"));
+
+        serde_json::from_str::<HexBlob>(&format!("\"0x{}0\"", hex)).unwrap_err();
+        serde_json::from_str::<HexBlob>(&format!("\"{}\"", hex)).unwrap_err();
+        serde_json::from_str::<HexBlob>("\"0x\"").unwrap_err();
+    }
+
+    #[test]
+    fn binary() {
+        let blob = blob();
+        let bytes = bincode::serialize(&blob).unwrap();
+        assert_eq!(bytes.len(), 8 + BYTES_PER_BLOB);
+        assert_eq!(bincode::deserialize::<HexBlob>(&bytes).unwrap(), blob);
+    }
+
+    crate::test_utils::option_wrapper!(Box<[u8; BYTES_PER_BLOB]>);
+
+    #[test]
+    fn option() {
+        crate::test_utils::check_option(
+            OptionWrapper {
+                val: Some(blob().value),
+            },
+            &serde_json::to_string(&blob()).unwrap(),
+            &[r#""0x00""#],
+        );
+    }
+}
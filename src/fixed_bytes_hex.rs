@@ -1,10 +1,211 @@
-//! Formats `[u8; n]` as a 0x-prefixed hex string.
+//! Formats `[u8; N]` as a 0x-prefixed hex string.
 //!
 //! E.g., `[0, 1, 2, 3]` serializes as `"0x00010203"`.
+//!
+//! The length of the array is inferred from the field type, so this module can be used directly
+//! for arrays of any size, e.g. `#[serde(with = "serde_utils::fixed_bytes_hex")]` on a `[u8; 48]`.
+//!
+//! Binary (non human-readable) formats store the raw bytes instead.
 
-use crate::hex::PrefixedHexVisitor;
-use serde::de::Error;
+use crate::hex::{serialize_prefixed, HexError, PrefixedHexArrayVisitor};
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::ops::{Deref, DerefMut};
 use serde::{Deserializer, Serializer};
+use serde_derive::{Deserialize, Serialize};
+
+pub fn serialize<S, const N: usize>(bytes: &[u8; N], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if !serializer.is_human_readable() {
+        return serializer.serialize_bytes(bytes);
+    }
+
+    serialize_prefixed(bytes, serializer)
+}
+
+pub fn deserialize<'de, D, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(PrefixedHexArrayVisitor)
+    } else {
+        deserializer.deserialize_bytes(PrefixedHexArrayVisitor)
+    }
+}
+
+/// The JSON schema of a 0x-prefixed hex string of exactly `N` bytes.
+///
+/// Usage: `#[schemars(schema_with = "fixed_bytes_hex::schema::<48>")]`.
+#[cfg(feature = "schemars")]
+pub fn schema<const N: usize>(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    crate::schema::fixed_hex_bytes(N)
+}
+
+/// Composable wrapper type for formatting `[u8; N]` as a 0x-prefixed hex string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct HexArray<const N: usize> {
+    #[serde(with = "self")]
+    pub value: [u8; N],
+}
+
+impl<const N: usize> Deref for HexArray<N> {
+    type Target = [u8; N];
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<const N: usize> DerefMut for HexArray<N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for HexArray<N> {
+    fn from(value: [u8; N]) -> Self {
+        Self { value }
+    }
+}
+
+impl<const N: usize> From<HexArray<N>> for [u8; N] {
+    fn from(wrapper: HexArray<N>) -> Self {
+        wrapper.value
+    }
+}
+
+#[cfg(feature = "schemars")]
+crate::schema::impl_json_schema!(
+    alloc::format!("HexArray{}", N),
+    [const N: usize] HexArray<N>,
+    crate::schema::fixed_hex_bytes(N)
+);
+
+/// Formats `Option<[u8; N]>` as a 0x-prefixed hex string or `null`.
+///
+/// Usage: `#[serde(with = "fixed_bytes_hex::option", default)]`.
+pub mod option {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    pub fn serialize<S, const N: usize>(
+        value: &Option<[u8; N]>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        value.map(HexArray::from).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D, const N: usize>(deserializer: D) -> Result<Option<[u8; N]>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<HexArray<N>>::deserialize(deserializer)?.map(<[u8; N]>::from))
+    }
+
+    #[cfg(feature = "schemars")]
+    pub fn schema<const N: usize>(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::schema::nullable(crate::schema::fixed_hex_bytes(N))
+    }
+}
+
+/// Formats `Box<[u8; N]>` as a 0x-prefixed hex string.
+///
+/// Intended for large arrays, which are decoded directly into a heap allocation rather than being
+/// built on the stack or in an intermediate `Vec`.
+///
+/// Usage: `#[serde(with = "fixed_bytes_hex::boxed")]`.
+pub mod boxed {
+    pub use super::serialize;
+    use super::*;
+    use crate::hex::decode_into;
+    use core::fmt;
+    use serde::de::{self, SeqAccess, Visitor};
+
+    /// Allocate a zeroed array on the heap, without building it on the stack first.
+    fn zeroed<const N: usize>() -> Box<[u8; N]> {
+        vec![0; N]
+            .into_boxed_slice()
+            .try_into()
+            .expect("slice has length N")
+    }
+
+    /// Deserializes a 0x-prefixed hex string of exactly `N` bytes into a `Box<[u8; N]>`.
+    ///
+    /// Like `PrefixedHexArrayVisitor`, raw bytes are accepted from binary formats.
+    pub struct BoxedHexArrayVisitor<const N: usize>;
+
+    impl<'de, const N: usize> Visitor<'de> for BoxedHexArrayVisitor<N> {
+        type Value = Box<[u8; N]>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a hex string with 0x prefix of {} bytes", N)
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            let mut array = zeroed();
+            decode_into(value, &mut *array).map_err(de::Error::custom)?;
+            Ok(array)
+        }
+
+        fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            self.visit_byte_buf(value.to_vec())
+        }
+
+        /// Takes ownership of the buffer when it has the correct length, without copying.
+        fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            let got = value.len();
+            value
+                .into_boxed_slice()
+                .try_into()
+                .map_err(|_| de::Error::custom(HexError::WrongLength { expected: N, got }))
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut array = zeroed::<N>();
+            for (i, byte) in array.iter_mut().enumerate() {
+                *byte = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+            }
+            if seq.next_element::<de::IgnoredAny>()?.is_some() {
+                return Err(de::Error::invalid_length(N + 1, &self));
+            }
+            Ok(array)
+        }
+    }
+
+    pub fn deserialize<'de, D, const N: usize>(deserializer: D) -> Result<Box<[u8; N]>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(BoxedHexArrayVisitor)
+        } else {
+            deserializer.deserialize_byte_buf(BoxedHexArrayVisitor)
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    pub use super::schema;
+}
 
 macro_rules! bytes_hex {
     ($num_bytes: tt) => {
@@ -16,29 +217,19 @@ macro_rules! bytes_hex {
         where
             S: Serializer,
         {
-            let mut hex_string: String = "0x".to_string();
-            hex_string.push_str(&hex::encode(&bytes));
-
-            serializer.serialize_str(&hex_string)
+            super::serialize(bytes, serializer)
         }
 
         pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; BYTES_LEN], D::Error>
         where
             D: Deserializer<'de>,
         {
-            let decoded = deserializer.deserialize_str(PrefixedHexVisitor)?;
-
-            if decoded.len() != BYTES_LEN {
-                return Err(D::Error::custom(format!(
-                    "expected {} bytes for array, got {}",
-                    BYTES_LEN,
-                    decoded.len()
-                )));
-            }
+            super::deserialize(deserializer)
+        }
 
-            let mut array = [0; BYTES_LEN];
-            array.copy_from_slice(&decoded);
-            Ok(array)
+        #[cfg(feature = "schemars")]
+        pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+            crate::schema::fixed_hex_bytes(BYTES_LEN)
         }
 
         #[cfg(test)]
@@ -143,3 +334,121 @@ pub mod bytes_4_hex {
 pub mod bytes_8_hex {
     bytes_hex!(8);
 }
+
+#[cfg(test)]
+mod test {
+    use super::HexArray;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Container {
+        #[serde(with = "super")]
+        root: [u8; 32],
+        #[serde(with = "super")]
+        pubkey: [u8; 48],
+        #[serde(with = "super")]
+        signature: [u8; 96],
+    }
+
+    #[test]
+    fn generic_lengths() {
+        let container = Container {
+            root: [1; 32],
+            pubkey: [2; 48],
+            signature: [3; 96],
+        };
+        let json = serde_json::to_string(&container).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                "{{\"root\":\"0x{}\",\"pubkey\":\"0x{}\",\"signature\":\"0x{}\"}}",
+                "01".repeat(32),
+                "02".repeat(48),
+                "03".repeat(96)
+            )
+        );
+        assert_eq!(serde_json::from_str::<Container>(&json).unwrap(), container);
+
+        // A 48-byte value in a 32-byte field.
+        let wrong_length = format!(
+            "{{\"root\":\"0x{}\",\"pubkey\":\"0x{}\",\"signature\":\"0x{}\"}}",
+            "01".repeat(48),
+            "02".repeat(48),
+            "03".repeat(96)
+        );
+        serde_json::from_str::<Container>(&wrong_length).unwrap_err();
+    }
+
+    #[test]
+    fn wrapper_composes() {
+        let values = vec![Some(HexArray::from([0xab; 4])), None];
+        let json = serde_json::to_string(&values).unwrap();
+        assert_eq!(json, "[\"0xabababab\",null]");
+        assert_eq!(
+            serde_json::from_str::<Vec<Option<HexArray<4>>>>(&json).unwrap(),
+            values
+        );
+        assert_eq!(*values[0].unwrap(), [0xab; 4]);
+
+        serde_json::from_str::<Vec<Option<HexArray<4>>>>("[\"0xababab\"]").unwrap_err();
+    }
+
+    crate::test_utils::option_wrapper!([u8; 48]);
+
+    #[test]
+    fn option() {
+        crate::test_utils::check_option(
+            OptionWrapper {
+                val: Some([0xab; 48]),
+            },
+            &format!(r#""0x{}""#, "ab".repeat(48)),
+            &[&format!(r#""0x{}""#, "ab".repeat(47))],
+        );
+    }
+
+    #[test]
+    fn binary() {
+        let container = Container {
+            root: [1; 32],
+            pubkey: [2; 48],
+            signature: [3; 96],
+        };
+        let bytes = bincode::serialize(&container).unwrap();
+        assert_eq!(bytes.len(), 3 * 8 + 32 + 48 + 96);
+        assert_eq!(
+            bincode::deserialize::<Container>(&bytes).unwrap(),
+            container
+        );
+
+        // The length is still checked.
+        let short = bincode::serialize(&HexArray::from([0u8; 31])).unwrap();
+        bincode::deserialize::<HexArray<32>>(&short).unwrap_err();
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct Boxed {
+        #[serde(with = "super::boxed")]
+        val: Box<[u8; 4]>,
+    }
+
+    #[test]
+    fn boxed() {
+        let boxed = Boxed {
+            val: Box::new([1, 2, 3, 4]),
+        };
+        let json = serde_json::to_string(&boxed).unwrap();
+        assert_eq!(json, "\"0x01020304\"");
+        assert_eq!(serde_json::from_str::<Boxed>(&json).unwrap(), boxed);
+
+        let err = serde_json::from_str::<Boxed>("\"0x010203\"").unwrap_err();
+        assert!(err.to_string().starts_with("expected 4 bytes, got 3"));
+        serde_json::from_str::<Boxed>("\"0x0102030405\"").unwrap_err();
+        serde_json::from_str::<Boxed>("\"01020304\"").unwrap_err();
+
+        let bytes = bincode::serialize(&boxed).unwrap();
+        assert_eq!(bincode::deserialize::<Boxed>(&bytes).unwrap(), boxed);
+        let short = bincode::serialize(&HexArray::from([0u8; 3])).unwrap();
+        bincode::deserialize::<Boxed>(&short).unwrap_err();
+    }
+}
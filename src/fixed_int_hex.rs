@@ -0,0 +1,127 @@
+//! Formats unsigned integers as fixed-width, 0x-prefixed, big-endian hex strings.
+//!
+//! E.g., `0u64` serializes as `"0x0000000000000000"`.
+//!
+//! Unlike the quantity encoding used by `u64_hex_be`, leading zeros are always emitted, and
+//! exactly `2 * size_of::<T>()` hex digits are required during decoding.
+//!
+//! Binary (non human-readable) formats store the integer natively.
+
+use crate::fixed_bytes_hex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+macro_rules! define_mod {
+    ($int: ty) => {
+        pub fn serialize<S>(num: &$int, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            if !serializer.is_human_readable() {
+                return num.serialize(serializer);
+            }
+
+            fixed_bytes_hex::serialize(&num.to_be_bytes(), serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<$int, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            if !deserializer.is_human_readable() {
+                return <$int>::deserialize(deserializer);
+            }
+
+            fixed_bytes_hex::deserialize(deserializer).map(<$int>::from_be_bytes)
+        }
+
+        #[cfg(feature = "schemars")]
+        pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+            crate::schema::fixed_hex_bytes(core::mem::size_of::<$int>())
+        }
+
+        #[cfg(test)]
+        mod test {
+            use serde::{Deserialize, Serialize};
+
+            const HEX_LEN: usize = 2 * core::mem::size_of::<$int>();
+
+            #[derive(Debug, PartialEq, Serialize, Deserialize)]
+            #[serde(transparent)]
+            struct Wrapper {
+                #[serde(with = "super")]
+                val: $int,
+            }
+
+            #[test]
+            fn encoding() {
+                assert_eq!(
+                    serde_json::to_string(&Wrapper { val: 0 }).unwrap(),
+                    format!("\"0x{}\"", "0".repeat(HEX_LEN))
+                );
+                assert_eq!(
+                    serde_json::to_string(&Wrapper { val: 0x1f }).unwrap(),
+                    format!("\"0x{}1f\"", "0".repeat(HEX_LEN - 2))
+                );
+                assert_eq!(
+                    serde_json::to_string(&Wrapper { val: <$int>::MAX }).unwrap(),
+                    format!("\"0x{}\"", "f".repeat(HEX_LEN))
+                );
+            }
+
+            #[test]
+            fn decoding() {
+                assert_eq!(
+                    serde_json::from_str::<Wrapper>(&format!("\"0x{}\"", "0".repeat(HEX_LEN)))
+                        .unwrap(),
+                    Wrapper { val: 0 },
+                );
+                assert_eq!(
+                    serde_json::from_str::<Wrapper>(&format!(
+                        "\"0x{}1f\"",
+                        "0".repeat(HEX_LEN - 2)
+                    ))
+                    .unwrap(),
+                    Wrapper { val: 0x1f },
+                );
+
+                // Quantity encoding is not accepted.
+                serde_json::from_str::<Wrapper>("\"0x1f\"").unwrap_err();
+                serde_json::from_str::<Wrapper>("\"0x0\"").unwrap_err();
+                // Wrong length.
+                serde_json::from_str::<Wrapper>(&format!("\"0x{}\"", "0".repeat(HEX_LEN - 1)))
+                    .unwrap_err();
+                serde_json::from_str::<Wrapper>(&format!("\"0x{}\"", "0".repeat(HEX_LEN + 2)))
+                    .unwrap_err();
+                // Requires 0x.
+                serde_json::from_str::<Wrapper>(&format!("\"{}\"", "0".repeat(HEX_LEN)))
+                    .unwrap_err();
+            }
+
+            #[test]
+            fn binary() {
+                let wrapper = Wrapper { val: 0x1f };
+                let bytes = bincode::serialize(&wrapper).unwrap();
+                assert_eq!(bytes, 0x1f_u64.to_le_bytes()[..HEX_LEN / 2]);
+                assert_eq!(bincode::deserialize::<Wrapper>(&bytes).unwrap(), wrapper);
+            }
+        }
+    };
+}
+
+pub mod u16_hex_be_fixed {
+    use super::*;
+
+    define_mod!(u16);
+}
+
+pub mod u32_hex_be_fixed {
+    use super::*;
+
+    define_mod!(u32);
+}
+
+pub mod u64_hex_be_fixed {
+    use super::*;
+
+    define_mod!(u64);
+}
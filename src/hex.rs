@@ -1,26 +1,243 @@
 //! Provides utilities for parsing 0x-prefixed hex strings.
 
-use serde::de::{self, Visitor};
-use std::fmt;
+use crate::hex_decode;
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Write};
+use serde::de::{self, SeqAccess, Visitor};
+use serde::Serializer;
+
+/// Errors that can occur whilst decoding hex strings and hex quantities.
+///
+/// Character indices are relative to the start of the input string, including any `0x` prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HexError {
+    /// The string does not start with `0x`.
+    MissingPrefix,
+    /// The string contains an odd number of hex digits.
+    OddLength,
+    /// The string contains a character that is not a hex digit.
+    InvalidChar { index: usize, char: char },
+    /// The decoded value does not have the expected number of bytes.
+    WrongLength { expected: usize, got: usize },
+    /// A quantity has a leading zero digit.
+    LeadingZero,
+    /// A quantity has no digits.
+    EmptyQuantity,
+    /// A quantity does not fit in the target integer type.
+    Overflow { bits: usize },
+    /// A mixed-case address does not match its EIP-55 checksum.
+    InvalidChecksum { expected: String },
+    /// The value contains more bytes than the maximum allowed.
+    ///
+    /// `got` is `None` if the value was rejected before its full length was known.
+    TooLong { max: usize, got: Option<usize> },
+    /// A list contains more byte lists than the maximum allowed.
+    ///
+    /// `got` is `None` if the list was rejected before its full length was known.
+    TooManyLists { max: usize, got: Option<usize> },
+}
+
+impl fmt::Display for HexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HexError::MissingPrefix => write!(f, "hex must have 0x prefix"),
+            HexError::OddLength => write!(f, "hex has an odd number of digits"),
+            HexError::InvalidChar { index, char } => {
+                write!(f, "invalid hex character {:?} at index {}", char, index)
+            }
+            HexError::WrongLength { expected, got } => {
+                write!(f, "expected {} bytes, got {}", expected, got)
+            }
+            HexError::LeadingZero => write!(f, "quantity cannot have leading zero"),
+            HexError::EmptyQuantity => write!(f, "quantity cannot be empty"),
+            HexError::Overflow { bits } => write!(f, "quantity exceeds {} bits", bits),
+            HexError::InvalidChecksum { expected } => {
+                write!(f, "invalid EIP-55 checksum, expected {}", expected)
+            }
+            HexError::TooLong {
+                max,
+                got: Some(got),
+            } => {
+                write!(
+                    f,
+                    "{} bytes exceeds the maximum length of {} bytes",
+                    got, max
+                )
+            }
+            HexError::TooLong { max, got: None } => {
+                write!(f, "value exceeds the maximum length of {} bytes", max)
+            }
+            HexError::TooManyLists {
+                max,
+                got: Some(got),
+            } => {
+                write!(
+                    f,
+                    "{} byte lists exceeds the maximum length of {} byte lists",
+                    got, max
+                )
+            }
+            HexError::TooManyLists { max, got: None } => {
+                write!(f, "list exceeds the maximum length of {} byte lists", max)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HexError {}
+
+impl HexError {
+    /// Convert an error from decoding `digits`, which start at `offset` within the input string.
+    pub(crate) fn from_hex(error: hex::FromHexError, digits: &str, offset: usize) -> Self {
+        match error {
+            hex::FromHexError::InvalidHexCharacter { index, .. } => HexError::InvalidChar {
+                index: offset + index,
+                // All characters before `index` are ASCII hex digits, so it's a char boundary.
+                char: digits[index..].chars().next().unwrap_or_default(),
+            },
+            // `InvalidStringLength` is only returned when decoding into a slice.
+            hex::FromHexError::OddLength | hex::FromHexError::InvalidStringLength => {
+                HexError::OddLength
+            }
+        }
+    }
+}
 
 /// Encode `data` as a 0x-prefixed hex string.
 pub fn encode<T: AsRef<[u8]>>(data: T) -> String {
-    let hex = hex::encode(data);
-
-    let mut s = "0x".to_string();
-    s.push_str(hex.as_str());
+    let data = data.as_ref();
+    let mut s = String::with_capacity(2 + 2 * data.len());
+    // Writing to a `String` never fails.
+    let _ = write!(s, "{}", PrefixedHex(data));
     s
 }
 
+/// Formats bytes as a 0x-prefixed hex string, without allocating.
+///
+/// Can be passed to `Serializer::collect_str` to stream hex directly to the output.
+#[derive(Debug, Clone, Copy)]
+pub struct PrefixedHex<'a>(pub &'a [u8]);
+
+impl fmt::Display for PrefixedHex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const CHUNK: usize = 64;
+        let mut buf = [0; 2 * CHUNK];
+
+        f.write_str("0x")?;
+        for chunk in self.0.chunks(CHUNK) {
+            f.write_str(encode_to_slice(chunk, &mut buf))?;
+        }
+        Ok(())
+    }
+}
+
+/// Encode `data` as unprefixed hex into the start of `buf`, returning the encoded string.
+///
+/// `buf` must have room for at least `2 * data.len()` bytes.
+fn encode_to_slice<'a>(data: &[u8], buf: &'a mut [u8]) -> &'a str {
+    let out = &mut buf[..2 * data.len()];
+    hex::encode_to_slice(data, out).expect("output has the correct length");
+    core::str::from_utf8(out).expect("hex digits are ASCII")
+}
+
+/// Serialize `bytes` as a 0x-prefixed hex string, without allocating.
+///
+/// Short inputs (up to the size of a BLS signature) are encoded on the stack and written with
+/// `serialize_str`, longer inputs are streamed with `collect_str`.
+pub(crate) fn serialize_prefixed<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    const MAX_STACK_BYTES: usize = 96;
+
+    if bytes.len() > MAX_STACK_BYTES {
+        return serializer.collect_str(&PrefixedHex(bytes));
+    }
+
+    let mut buf = [0; 2 + 2 * MAX_STACK_BYTES];
+    buf[..2].copy_from_slice(b"0x");
+    let len = 2 + encode_to_slice(bytes, &mut buf[2..]).len();
+    serializer.serialize_str(core::str::from_utf8(&buf[..len]).expect("hex digits are ASCII"))
+}
+
 /// Decode `data` from a 0x-prefixed hex string.
-pub fn decode(s: &str) -> Result<Vec<u8>, String> {
+pub fn decode(s: &str) -> Result<Vec<u8>, HexError> {
     if let Some(stripped) = s.strip_prefix("0x") {
-        hex::decode(stripped).map_err(|e| format!("invalid hex: {:?}", e))
+        hex_decode::decode(stripped.as_bytes()).map_err(|e| HexError::from_hex(e, stripped, 2))
     } else {
-        Err("hex must have 0x prefix".to_string())
+        Err(HexError::MissingPrefix)
+    }
+}
+
+/// Decode `data` from a 0x-prefixed hex string of at most `max` bytes.
+///
+/// Longer strings are rejected before any decoding takes place.
+pub fn decode_bounded(s: &str, max: usize) -> Result<Vec<u8>, HexError> {
+    let digits = s.strip_prefix("0x").ok_or(HexError::MissingPrefix)?;
+
+    let got = digits.len().div_ceil(2);
+    if got > max {
+        return Err(HexError::TooLong {
+            max,
+            got: Some(got),
+        });
     }
+    decode(s)
+}
+
+/// Decode a 0x-prefixed hex string into `out`, without allocating.
+///
+/// The string must contain exactly `out.len()` bytes. Errors are reported in the same order as
+/// `decode` followed by a length check, i.e. an invalid character takes precedence over a
+/// `WrongLength` error.
+pub fn decode_into(s: &str, out: &mut [u8]) -> Result<(), HexError> {
+    let digits = s.strip_prefix("0x").ok_or(HexError::MissingPrefix)?;
+
+    if !digits.len().is_multiple_of(2) {
+        return Err(HexError::OddLength);
+    }
+    if digits.len() != 2 * out.len() {
+        if let Some((index, char)) = digits.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+            return Err(HexError::InvalidChar {
+                index: index + 2,
+                char,
+            });
+        }
+        return Err(HexError::WrongLength {
+            expected: out.len(),
+            got: digits.len() / 2,
+        });
+    }
+
+    hex_decode::decode_to_slice(digits.as_bytes(), out)
+        .map_err(|e| HexError::from_hex(e, digits, 2))
 }
 
+/// Decode a 0x-prefixed hex string of exactly `N` bytes, without allocating.
+pub fn decode_to_array<const N: usize>(s: &str) -> Result<[u8; N], HexError> {
+    let mut array = [0; N];
+    decode_into(s, &mut array)?;
+    Ok(array)
+}
+
+/// Collect a sequence of `u8`s, for formats which represent bytes as a list of integers.
+fn visit_byte_seq<'de, A>(mut seq: A) -> Result<Vec<u8>, A::Error>
+where
+    A: SeqAccess<'de>,
+{
+    // Don't trust the size hint with a large allocation.
+    let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+    while let Some(byte) = seq.next_element()? {
+        bytes.push(byte);
+    }
+    Ok(bytes)
+}
+
+/// Deserializes a 0x-prefixed hex string into a `Vec<u8>`.
+///
+/// Binary formats may instead provide the raw bytes, either as bytes or as a sequence of `u8`s.
 pub struct PrefixedHexVisitor;
 
 impl<'de> Visitor<'de> for PrefixedHexVisitor {
@@ -36,6 +253,173 @@ impl<'de> Visitor<'de> for PrefixedHexVisitor {
     {
         decode(value).map_err(de::Error::custom)
     }
+
+    /// Raw bytes, as written by binary (non human-readable) formats.
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(value.to_vec())
+    }
+
+    fn visit_borrowed_bytes<E>(self, value: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_bytes(value)
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(value)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        visit_byte_seq(seq)
+    }
+}
+
+/// Deserializes a 0x-prefixed hex string of at most `MAX` bytes into a `Vec<u8>`.
+///
+/// Like `PrefixedHexVisitor`, raw bytes are accepted from binary formats, subject to the same
+/// limit.
+pub struct BoundedPrefixedHexVisitor<const MAX: usize>;
+
+impl<const MAX: usize> BoundedPrefixedHexVisitor<MAX> {
+    fn check_len<E>(len: usize) -> Result<(), E>
+    where
+        E: de::Error,
+    {
+        if len > MAX {
+            Err(de::Error::custom(HexError::TooLong {
+                max: MAX,
+                got: Some(len),
+            }))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<'de, const MAX: usize> Visitor<'de> for BoundedPrefixedHexVisitor<MAX> {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "a hex string with 0x prefix of at most {} bytes",
+            MAX
+        )
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        decode_bounded(value, MAX).map_err(de::Error::custom)
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Self::check_len(value.len())?;
+        Ok(value.to_vec())
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Self::check_len(value.len())?;
+        Ok(value)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(MAX).min(4096));
+        while let Some(byte) = seq.next_element()? {
+            if bytes.len() == MAX {
+                return Err(de::Error::custom(HexError::TooLong {
+                    max: MAX,
+                    got: None,
+                }));
+            }
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+/// Deserializes a 0x-prefixed hex string of exactly `N` bytes into a `[u8; N]`, without
+/// allocating.
+///
+/// Like `PrefixedHexVisitor`, raw bytes are accepted from binary formats.
+pub struct PrefixedHexArrayVisitor<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for PrefixedHexArrayVisitor<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a hex string with 0x prefix of {} bytes", N)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        decode_to_array(value).map_err(de::Error::custom)
+    }
+
+    /// Raw bytes, as written by binary (non human-readable) formats.
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        <[u8; N]>::try_from(value).map_err(|_| {
+            de::Error::custom(HexError::WrongLength {
+                expected: N,
+                got: value.len(),
+            })
+        })
+    }
+
+    fn visit_borrowed_bytes<E>(self, value: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_bytes(value)
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_bytes(&value)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut array = [0; N];
+        for (i, byte) in array.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(N + 1, &self));
+        }
+        Ok(array)
+    }
 }
 
 pub struct HexVisitor;
@@ -51,8 +435,39 @@ impl<'de> Visitor<'de> for HexVisitor {
     where
         E: de::Error,
     {
-        hex::decode(value.trim_start_matches("0x"))
-            .map_err(|e| de::Error::custom(format!("invalid hex ({:?})", e)))
+        let stripped = value.trim_start_matches("0x");
+        let offset = value.len() - stripped.len();
+        hex_decode::decode(stripped.as_bytes())
+            .map_err(|e| de::Error::custom(HexError::from_hex(e, stripped, offset)))
+    }
+
+    /// Raw bytes, as written by binary (non human-readable) formats.
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(value.to_vec())
+    }
+
+    fn visit_borrowed_bytes<E>(self, value: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_bytes(value)
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(value)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        visit_byte_seq(seq)
     }
 }
 
@@ -73,5 +488,184 @@ mod test {
         let bytes = vec![1, 2, 3];
         let hex = encode(bytes);
         assert_eq!(hex.as_str(), "0x010203");
+
+        // Spans several chunks of the `Display` implementation.
+        let bytes: Vec<u8> = (0..=255).collect();
+        let hex = encode(&bytes);
+        assert_eq!(hex, format!("0x{}", hex::encode(&bytes)));
+        assert_eq!(hex.capacity(), hex.len());
+        assert_eq!(PrefixedHex(&bytes).to_string(), hex);
+    }
+
+    #[test]
+    fn serialize_prefixed_lengths() {
+        for len in [0, 1, 95, 96, 97, 1000] {
+            let bytes = vec![0xa5; len];
+            let mut json = vec![];
+            serialize_prefixed(&bytes, &mut serde_json::Serializer::new(&mut json)).unwrap();
+            assert_eq!(json, format!("\"0x{}\"", "a5".repeat(len)).into_bytes());
+        }
+    }
+
+    #[test]
+    fn decoding_errors() {
+        assert_eq!(decode("00ff"), Err(HexError::MissingPrefix));
+        assert_eq!(decode("0x0ff"), Err(HexError::OddLength));
+        assert_eq!(
+            decode("0x00fg"),
+            Err(HexError::InvalidChar {
+                index: 5,
+                char: 'g'
+            })
+        );
+        assert_eq!(
+            decode("0x00é"),
+            Err(HexError::InvalidChar {
+                index: 4,
+                char: 'é'
+            })
+        );
+    }
+
+    #[test]
+    fn visitor_errors() {
+        let err = HexVisitor
+            .visit_str::<serde_json::Error>("0x0g")
+            .unwrap_err();
+        assert_eq!(err.to_string(), "invalid hex character 'g' at index 3");
+
+        let err = PrefixedHexVisitor
+            .visit_str::<serde_json::Error>("00")
+            .unwrap_err();
+        assert_eq!(err.to_string(), "hex must have 0x prefix");
+    }
+
+    #[test]
+    fn decoding_into_slice() {
+        let mut out = [0; 2];
+        decode_into("0x00ff", &mut out).unwrap();
+        assert_eq!(out, [0, 255]);
+        assert_eq!(decode_to_array::<0>("0x"), Ok([]));
+        assert_eq!(decode_to_array::<3>("0x010203"), Ok([1, 2, 3]));
+
+        assert_eq!(decode_to_array::<2>("00ff"), Err(HexError::MissingPrefix));
+        assert_eq!(decode_to_array::<2>("0x0ff"), Err(HexError::OddLength));
+        assert_eq!(
+            decode_to_array::<2>("0x00"),
+            Err(HexError::WrongLength {
+                expected: 2,
+                got: 1
+            })
+        );
+        assert_eq!(
+            decode_to_array::<2>("0x000000"),
+            Err(HexError::WrongLength {
+                expected: 2,
+                got: 3
+            })
+        );
+
+        // Invalid characters are reported before the length, as for `decode`.
+        for s in ["0x00fg", "0x0g", "0x00fg00", "0x00é"] {
+            assert_eq!(
+                decode_to_array::<2>(s).unwrap_err(),
+                decode(s).unwrap_err(),
+                "{}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn visitor_inputs() {
+        use serde::de::value::{
+            BorrowedBytesDeserializer, BorrowedStrDeserializer, BytesDeserializer, Error,
+            SeqDeserializer, StringDeserializer,
+        };
+        use serde::Deserializer;
+
+        let expected = vec![0, 255];
+
+        let de = BorrowedStrDeserializer::<Error>::new("0x00ff");
+        assert_eq!(de.deserialize_str(PrefixedHexVisitor).unwrap(), expected);
+        let de = StringDeserializer::<Error>::new("0x00ff".to_string());
+        assert_eq!(de.deserialize_str(PrefixedHexVisitor).unwrap(), expected);
+        let de = StringDeserializer::<Error>::new("00ff".to_string());
+        assert_eq!(de.deserialize_str(HexVisitor).unwrap(), expected);
+
+        // Bytes and sequences are raw bytes, not hex.
+        let de = BytesDeserializer::<Error>::new(&[0, 255]);
+        assert_eq!(de.deserialize_bytes(PrefixedHexVisitor).unwrap(), expected);
+        let de = BorrowedBytesDeserializer::<Error>::new(&[0, 255]);
+        assert_eq!(de.deserialize_bytes(HexVisitor).unwrap(), expected);
+        let de = SeqDeserializer::<_, Error>::new(expected.clone().into_iter());
+        assert_eq!(de.deserialize_seq(PrefixedHexVisitor).unwrap(), expected);
+
+        // Fixed-length arrays.
+        let de = BorrowedStrDeserializer::<Error>::new("0x00ff");
+        assert_eq!(
+            de.deserialize_str(PrefixedHexArrayVisitor::<2>).unwrap(),
+            [0, 255]
+        );
+        let de = BytesDeserializer::<Error>::new(&[0, 255]);
+        assert_eq!(
+            de.deserialize_bytes(PrefixedHexArrayVisitor::<2>).unwrap(),
+            [0, 255]
+        );
+        let de = BytesDeserializer::<Error>::new(&[0]);
+        de.deserialize_bytes(PrefixedHexArrayVisitor::<2>)
+            .unwrap_err();
+        for len in [1, 3] {
+            let de = SeqDeserializer::<_, Error>::new(vec![0u8; len].into_iter());
+            de.deserialize_seq(PrefixedHexArrayVisitor::<2>)
+                .unwrap_err();
+        }
+
+        // `serde_json::Value` hands out owned strings.
+        let value = serde_json::Value::String("0x00ff".to_string());
+        assert_eq!(value.deserialize_str(PrefixedHexVisitor).unwrap(), expected);
+    }
+
+    #[test]
+    fn decoding_bounded() {
+        use serde::de::value::{Error, SeqDeserializer};
+        use serde::Deserializer;
+
+        assert_eq!(decode_bounded("0x0001", 2), Ok(vec![0, 1]));
+        assert_eq!(decode_bounded("0x", 0), Ok(vec![]));
+        assert_eq!(
+            decode_bounded("0x000102", 2),
+            Err(HexError::TooLong {
+                max: 2,
+                got: Some(3)
+            })
+        );
+        // Rejected by length before looking at the digits.
+        assert_eq!(
+            decode_bounded("0xzzzzz", 2),
+            Err(HexError::TooLong {
+                max: 2,
+                got: Some(3)
+            })
+        );
+        assert_eq!(decode_bounded("0x000", 2), Err(HexError::OddLength));
+        assert_eq!(decode_bounded("0001", 2), Err(HexError::MissingPrefix));
+        assert_eq!(
+            HexError::TooLong {
+                max: 2,
+                got: Some(3)
+            }
+            .to_string(),
+            "3 bytes exceeds the maximum length of 2 bytes"
+        );
+
+        // The length of a sequence is unknown once it exceeds the maximum.
+        let de = SeqDeserializer::<_, Error>::new(vec![0u8; 3].into_iter());
+        assert_eq!(
+            de.deserialize_seq(BoundedPrefixedHexVisitor::<2>)
+                .unwrap_err()
+                .to_string(),
+            "value exceeds the maximum length of 2 bytes"
+        );
     }
 }
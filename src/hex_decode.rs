@@ -0,0 +1,366 @@
+//! Hex decoding with SIMD fast paths for large inputs, such as blobs.
+//!
+//! Behaves exactly like `hex::decode` and `hex::decode_to_slice`, including which error is
+//! returned for invalid input. The SIMD paths only decode blocks made entirely of hex digits, and
+//! stop at the first block containing anything else, which the scalar decoder then reports.
+//!
+//! AVX2 and SSSE3 are detected at runtime on x86_64, and NEON is always available on aarch64.
+//! Without the `std` feature runtime detection is unavailable, so on x86_64 only the features
+//! enabled at compile time (e.g. with `-C target-cpu=native`) are used.
+
+use alloc::{vec, vec::Vec};
+use hex::FromHexError;
+
+const INVALID: u8 = 0xff;
+
+/// Maps each byte to the value of the hex digit it represents, or `INVALID`.
+static NIBBLES: [u8; 256] = {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+    while i < 10 {
+        table[b'0' as usize + i] = i as u8;
+        i += 1;
+    }
+    let mut i = 0;
+    while i < 6 {
+        table[b'a' as usize + i] = 10 + i as u8;
+        table[b'A' as usize + i] = 10 + i as u8;
+        i += 1;
+    }
+    table
+};
+
+/// Equivalent to `hex::decode`.
+pub(crate) fn decode(digits: &[u8]) -> Result<Vec<u8>, FromHexError> {
+    if !digits.len().is_multiple_of(2) {
+        return Err(FromHexError::OddLength);
+    }
+    let mut out = vec![0; digits.len() / 2];
+    decode_to_slice(digits, &mut out)?;
+    Ok(out)
+}
+
+/// Equivalent to `hex::decode_to_slice`.
+pub(crate) fn decode_to_slice(digits: &[u8], out: &mut [u8]) -> Result<(), FromHexError> {
+    if !digits.len().is_multiple_of(2) {
+        return Err(FromHexError::OddLength);
+    }
+    if digits.len() / 2 != out.len() {
+        return Err(FromHexError::InvalidStringLength);
+    }
+
+    let done = decode_simd(digits, out);
+    decode_scalar(&digits[2 * done..], &mut out[done..], 2 * done)
+}
+
+/// Decode `digits` into `out`, which must be half as long. `offset` is the index of the first
+/// digit within the whole input, for error reporting.
+fn decode_scalar(digits: &[u8], out: &mut [u8], offset: usize) -> Result<(), FromHexError> {
+    for (i, (pair, byte)) in digits.chunks_exact(2).zip(out).enumerate() {
+        let index = offset + 2 * i;
+        *byte = nibble(pair[0], index)? << 4 | nibble(pair[1], index + 1)?;
+    }
+    Ok(())
+}
+
+fn nibble(c: u8, index: usize) -> Result<u8, FromHexError> {
+    match NIBBLES[c as usize] {
+        INVALID => Err(FromHexError::InvalidHexCharacter {
+            c: c as char,
+            index,
+        }),
+        n => Ok(n),
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "std"))]
+macro_rules! has_x86_feature {
+    ($feature: tt) => {
+        is_x86_feature_detected!($feature)
+    };
+}
+
+#[cfg(all(target_arch = "x86_64", not(feature = "std")))]
+macro_rules! has_x86_feature {
+    ($feature: tt) => {
+        cfg!(target_feature = $feature)
+    };
+}
+
+/// Decode as many whole blocks as possible, returning the number of bytes written to `out`.
+#[cfg(target_arch = "x86_64")]
+// Without `std` both conditions are constants, and may be equal.
+#[cfg_attr(not(feature = "std"), allow(clippy::ifs_same_cond))]
+fn decode_simd(digits: &[u8], out: &mut [u8]) -> usize {
+    if has_x86_feature!("avx2") {
+        // SAFETY: the required CPU feature is available.
+        unsafe { x86::decode_avx2(digits, out) }
+    } else if has_x86_feature!("ssse3") {
+        // SAFETY: the required CPU feature is available.
+        unsafe { x86::decode_ssse3(digits, out) }
+    } else {
+        0
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+fn decode_simd(digits: &[u8], out: &mut [u8]) -> usize {
+    // SAFETY: NEON is part of the aarch64 baseline.
+    unsafe { neon::decode(digits, out) }
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn decode_simd(_: &[u8], _: &mut [u8]) -> usize {
+    0
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use core::arch::x86_64::*;
+
+    /// Convert 16 hex digits to their values, or `None` if any of them is not a hex digit.
+    #[inline]
+    #[target_feature(enable = "ssse3")]
+    unsafe fn nibbles(v: __m128i) -> Option<__m128i> {
+        // Digits and letters are offset so that valid values are the smallest unsigned bytes.
+        let digit = _mm_sub_epi8(v, _mm_set1_epi8(b'0' as i8));
+        let is_digit = _mm_cmpeq_epi8(_mm_min_epu8(digit, _mm_set1_epi8(9)), digit);
+        let letter = _mm_sub_epi8(
+            _mm_or_si128(v, _mm_set1_epi8(0x20)),
+            _mm_set1_epi8(b'a' as i8),
+        );
+        let is_letter = _mm_cmpeq_epi8(_mm_min_epu8(letter, _mm_set1_epi8(5)), letter);
+
+        if _mm_movemask_epi8(_mm_or_si128(is_digit, is_letter)) != 0xffff {
+            return None;
+        }
+        let letter = _mm_add_epi8(letter, _mm_set1_epi8(10));
+        Some(_mm_or_si128(
+            _mm_and_si128(is_digit, digit),
+            _mm_andnot_si128(is_digit, letter),
+        ))
+    }
+
+    /// Decodes 32 digits into 16 bytes per iteration.
+    #[target_feature(enable = "ssse3")]
+    pub(super) unsafe fn decode_ssse3(digits: &[u8], out: &mut [u8]) -> usize {
+        // Multiplying each pair of nibbles by `[16, 1]` and summing gives the byte value.
+        let weights = _mm_set1_epi16(0x0110);
+        let mut done = 0;
+
+        for (block, dst) in digits.chunks_exact(32).zip(out.chunks_exact_mut(16)) {
+            let lo = _mm_loadu_si128(block.as_ptr() as *const __m128i);
+            let hi = _mm_loadu_si128(block.as_ptr().add(16) as *const __m128i);
+            let (Some(lo), Some(hi)) = (nibbles(lo), nibbles(hi)) else {
+                break;
+            };
+            let bytes = _mm_packus_epi16(
+                _mm_maddubs_epi16(lo, weights),
+                _mm_maddubs_epi16(hi, weights),
+            );
+            _mm_storeu_si128(dst.as_mut_ptr() as *mut __m128i, bytes);
+            done += 16;
+        }
+        done
+    }
+
+    /// The AVX2 equivalent of `nibbles`, for 32 hex digits.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn nibbles_256(v: __m256i) -> Option<__m256i> {
+        let digit = _mm256_sub_epi8(v, _mm256_set1_epi8(b'0' as i8));
+        let is_digit = _mm256_cmpeq_epi8(_mm256_min_epu8(digit, _mm256_set1_epi8(9)), digit);
+        let letter = _mm256_sub_epi8(
+            _mm256_or_si256(v, _mm256_set1_epi8(0x20)),
+            _mm256_set1_epi8(b'a' as i8),
+        );
+        let is_letter = _mm256_cmpeq_epi8(_mm256_min_epu8(letter, _mm256_set1_epi8(5)), letter);
+
+        if _mm256_movemask_epi8(_mm256_or_si256(is_digit, is_letter)) != -1 {
+            return None;
+        }
+        let letter = _mm256_add_epi8(letter, _mm256_set1_epi8(10));
+        Some(_mm256_or_si256(
+            _mm256_and_si256(is_digit, digit),
+            _mm256_andnot_si256(is_digit, letter),
+        ))
+    }
+
+    /// Decodes 64 digits into 32 bytes per iteration.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn decode_avx2(digits: &[u8], out: &mut [u8]) -> usize {
+        let weights = _mm256_set1_epi16(0x0110);
+        let mut done = 0;
+
+        for (block, dst) in digits.chunks_exact(64).zip(out.chunks_exact_mut(32)) {
+            let lo = _mm256_loadu_si256(block.as_ptr() as *const __m256i);
+            let hi = _mm256_loadu_si256(block.as_ptr().add(32) as *const __m256i);
+            let (Some(lo), Some(hi)) = (nibbles_256(lo), nibbles_256(hi)) else {
+                break;
+            };
+            // Packing works within each 128-bit lane, so the 64-bit quarters of the result are
+            // ordered `[lo0, hi0, lo1, hi1]` and need swapping back into place.
+            let bytes = _mm256_packus_epi16(
+                _mm256_maddubs_epi16(lo, weights),
+                _mm256_maddubs_epi16(hi, weights),
+            );
+            let bytes = _mm256_permute4x64_epi64::<0b11_01_10_00>(bytes);
+            _mm256_storeu_si256(dst.as_mut_ptr() as *mut __m256i, bytes);
+            done += 32;
+        }
+
+        // Finish any remaining 32-digit block with SSSE3, which AVX2 implies.
+        done + decode_ssse3(&digits[2 * done..], &mut out[done..])
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use core::arch::aarch64::*;
+
+    /// Convert 16 hex digits to their values, or `None` if any of them is not a hex digit.
+    #[inline]
+    #[target_feature(enable = "neon")]
+    unsafe fn nibbles(v: uint8x16_t) -> Option<uint8x16_t> {
+        let digit = vsubq_u8(v, vdupq_n_u8(b'0'));
+        let is_digit = vcleq_u8(digit, vdupq_n_u8(9));
+        let letter = vsubq_u8(vorrq_u8(v, vdupq_n_u8(0x20)), vdupq_n_u8(b'a'));
+        let is_letter = vcleq_u8(letter, vdupq_n_u8(5));
+
+        if vminvq_u8(vorrq_u8(is_digit, is_letter)) != 0xff {
+            return None;
+        }
+        Some(vbslq_u8(is_digit, digit, vaddq_u8(letter, vdupq_n_u8(10))))
+    }
+
+    /// Decodes 32 digits into 16 bytes per iteration.
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn decode(digits: &[u8], out: &mut [u8]) -> usize {
+        let mut done = 0;
+
+        for (block, dst) in digits.chunks_exact(32).zip(out.chunks_exact_mut(16)) {
+            // De-interleave into the high and low nibble of each byte.
+            let pairs = vld2q_u8(block.as_ptr());
+            let (Some(hi), Some(lo)) = (nibbles(pairs.0), nibbles(pairs.1)) else {
+                break;
+            };
+            vst1q_u8(dst.as_mut_ptr(), vorrq_u8(vshlq_n_u8::<4>(hi), lo));
+            done += 16;
+        }
+        done
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Deterministic xorshift generator, so that failures are reproducible.
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+    }
+
+    const HEX_DIGITS: &[u8] = b"0123456789abcdefABCDEF";
+    // Bytes next to the valid ranges, and some non-ASCII bytes.
+    const NON_HEX: &[u8] = b"/:@G`gxX \0\x7f\x80\xc3\xff";
+
+    /// Random digits, with a few invalid characters mixed into some of the inputs.
+    fn random_input(rng: &mut XorShift) -> Vec<u8> {
+        let len = match rng.below(4) {
+            0 => rng.below(8),
+            1 => rng.below(200),
+            _ => rng.below(1000),
+        };
+        let mut input: Vec<u8> = (0..len)
+            .map(|_| HEX_DIGITS[rng.below(HEX_DIGITS.len())])
+            .collect();
+        if len > 0 && rng.below(2) == 0 {
+            for _ in 0..=rng.below(3) {
+                let index = rng.below(len);
+                input[index] = NON_HEX[rng.below(NON_HEX.len())];
+            }
+        }
+        input
+    }
+
+    fn expected(input: &[u8]) -> Result<Vec<u8>, FromHexError> {
+        hex::decode(input)
+    }
+
+    /// Run one of the SIMD paths followed by the scalar decoder, as `decode_to_slice` does.
+    fn decode_with(
+        input: &[u8],
+        simd: impl Fn(&[u8], &mut [u8]) -> usize,
+    ) -> Result<Vec<u8>, FromHexError> {
+        if !input.len().is_multiple_of(2) {
+            return Err(FromHexError::OddLength);
+        }
+        let mut out = vec![0; input.len() / 2];
+        let done = simd(input, &mut out);
+        decode_scalar(&input[2 * done..], &mut out[done..], 2 * done)?;
+        Ok(out)
+    }
+
+    #[test]
+    fn differential() {
+        let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..20_000 {
+            let input = random_input(&mut rng);
+            let expected = expected(&input);
+
+            assert_eq!(decode(&input), expected, "input: {:?}", input);
+            assert_eq!(decode_with(&input, |_, _| 0), expected);
+
+            #[cfg(target_arch = "x86_64")]
+            {
+                if is_x86_feature_detected!("ssse3") {
+                    let ssse3 = |d: &[u8], o: &mut [u8]| unsafe { x86::decode_ssse3(d, o) };
+                    assert_eq!(decode_with(&input, ssse3), expected);
+                }
+                if has_x86_feature!("avx2") {
+                    let avx2 = |d: &[u8], o: &mut [u8]| unsafe { x86::decode_avx2(d, o) };
+                    assert_eq!(decode_with(&input, avx2), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_byte_at_every_block_position() {
+        // A single invalid (or valid) byte at each position of the first two 64-digit blocks.
+        for byte in 0..=255u8 {
+            for index in 0..128 {
+                let mut input = vec![b'a'; 160];
+                input[index] = byte;
+                assert_eq!(decode(&input), expected(&input), "{} at {}", byte, index);
+            }
+        }
+    }
+
+    #[test]
+    fn slice_errors() {
+        let mut out = [0; 2];
+        assert_eq!(
+            decode_to_slice(b"abc", &mut out),
+            Err(FromHexError::OddLength)
+        );
+        assert_eq!(
+            decode_to_slice(b"ab", &mut out),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(decode_to_slice(b"abCD", &mut out), Ok(()));
+        assert_eq!(out, [0xab, 0xcd]);
+    }
+}
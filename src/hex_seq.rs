@@ -0,0 +1,383 @@
+//! Formats a collection of byte strings as a list of 0x-prefixed hex strings.
+//!
+//! E.g., `vec![B256::ZERO]` serializes as `["0x0000...0000"]`.
+//!
+//! Works for any element type implementing `HexItem` (`Vec<u8>`, `[u8; N]`, and alloy's `B256`,
+//! `Address` and `Bytes` with the `alloy` feature), and any collection that can be iterated by
+//! reference and built with `FromIterator`, such as `Vec`, `VecDeque`, `BTreeSet` and `SmallVec`.
+//! Fixed-length arrays are supported by the `array` submodule.
+//!
+//! Each element is formatted by the module for its type, e.g. `b256_hex` for `B256`, so binary
+//! (non human-readable) formats store the raw bytes of each element.
+
+use crate::{fixed_bytes_hex, hex_vec, seq};
+use alloc::vec::Vec;
+#[cfg(feature = "alloy")]
+use alloy_primitives::{Address, Bytes, B256};
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{DeserializeSeed, SeqAccess, Visitor};
+use serde::ser::Serialize;
+use serde::{Deserializer, Serializer};
+
+/// A byte string which can be formatted as a 0x-prefixed hex string.
+pub trait HexItem: Sized {
+    /// Serialize as a 0x-prefixed hex string.
+    fn serialize_hex<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+
+    /// Deserialize from a 0x-prefixed hex string.
+    fn deserialize_hex<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+
+    /// The JSON schema of the hex string.
+    ///
+    /// Defaults to a hex string of any length.
+    #[cfg(feature = "schemars")]
+    fn json_schema() -> schemars::Schema {
+        crate::schema::hex_bytes()
+    }
+}
+
+impl HexItem for Vec<u8> {
+    fn serialize_hex<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        hex_vec::serialize(self, serializer)
+    }
+
+    fn deserialize_hex<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        hex_vec::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "alloy")]
+impl HexItem for Bytes {
+    fn serialize_hex<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        hex_vec::serialize(self, serializer)
+    }
+
+    fn deserialize_hex<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        hex_vec::deserialize(deserializer).map(Into::into)
+    }
+}
+
+impl<const N: usize> HexItem for [u8; N] {
+    fn serialize_hex<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        fixed_bytes_hex::serialize(self, serializer)
+    }
+
+    fn deserialize_hex<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        fixed_bytes_hex::deserialize(deserializer)
+    }
+
+    #[cfg(feature = "schemars")]
+    fn json_schema() -> schemars::Schema {
+        crate::schema::fixed_hex_bytes(N)
+    }
+}
+
+#[cfg(feature = "alloy")]
+impl HexItem for B256 {
+    fn serialize_hex<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        crate::b256_hex::serialize(self, serializer)
+    }
+
+    fn deserialize_hex<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::b256_hex::deserialize(deserializer)
+    }
+
+    #[cfg(feature = "schemars")]
+    fn json_schema() -> schemars::Schema {
+        crate::schema::fixed_hex_bytes(32)
+    }
+}
+
+#[cfg(feature = "alloy")]
+impl HexItem for Address {
+    fn serialize_hex<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        crate::address_hex::serialize(self, serializer)
+    }
+
+    fn deserialize_hex<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::address_hex::deserialize(deserializer)
+    }
+
+    #[cfg(feature = "schemars")]
+    fn json_schema() -> schemars::Schema {
+        crate::schema::fixed_hex_bytes(20)
+    }
+}
+
+/// Serializes a single element as hex.
+struct HexElement<'a, T>(&'a T);
+
+impl<T: HexItem> Serialize for HexElement<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize_hex(serializer)
+    }
+}
+
+/// Deserializes a single element from hex.
+struct HexElementSeed<T>(PhantomData<T>);
+
+impl<'de, T: HexItem> DeserializeSeed<'de> for HexElementSeed<T> {
+    type Value = T;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize_hex(deserializer)
+    }
+}
+
+/// Serde support for deserializing a list of hex strings into any `FromIterator` collection.
+pub struct HexSeqVisitor<C, T> {
+    _phantom: PhantomData<(C, T)>,
+}
+
+impl<C, T> HexSeqVisitor<C, T> {
+    pub const fn new() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<C, T> Default for HexSeqVisitor<C, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'de, C, T> Visitor<'de> for HexSeqVisitor<C, T>
+where
+    C: FromIterator<T>,
+    T: HexItem,
+{
+    type Value = C;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a list of 0x-prefixed hex strings")
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        seq::collect(seq, || HexElementSeed(PhantomData))
+    }
+}
+
+pub fn serialize<S, C, T>(value: &C, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    C: ?Sized,
+    for<'a> &'a C: IntoIterator<Item = &'a T>,
+    T: HexItem,
+{
+    serializer.collect_seq(value.into_iter().map(HexElement))
+}
+
+pub fn deserialize<'de, D, C, T>(deserializer: D) -> Result<C, D::Error>
+where
+    D: Deserializer<'de>,
+    C: FromIterator<T>,
+    T: HexItem,
+{
+    deserializer.deserialize_seq(HexSeqVisitor::new())
+}
+
+/// The JSON schema of a list of hex strings, each formatted as for `T`.
+///
+/// Usage: `#[schemars(schema_with = "hex_seq::schema::<B256>")]`.
+#[cfg(feature = "schemars")]
+pub fn schema<T: HexItem>(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    crate::schema::array(T::json_schema(), None)
+}
+
+/// Formats `[T; N]` as a list of 0x-prefixed hex strings.
+///
+/// Lists of any other length are rejected during decoding.
+pub mod array {
+    pub use super::serialize;
+    use super::*;
+
+    pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+    where
+        D: Deserializer<'de>,
+        T: HexItem,
+    {
+        seq::to_array(super::deserialize::<_, Vec<T>, T>(deserializer)?)
+    }
+
+    /// The JSON schema of a list of exactly `N` hex strings.
+    ///
+    /// Usage: `#[schemars(schema_with = "hex_seq::array::schema::<B256, 2>")]`.
+    #[cfg(feature = "schemars")]
+    pub fn schema<T: HexItem, const N: usize>(
+        _: &mut schemars::SchemaGenerator,
+    ) -> schemars::Schema {
+        crate::schema::array(T::json_schema(), Some(N))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    use smallvec::SmallVec;
+    use std::collections::VecDeque;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Obj {
+        #[serde(with = "super")]
+        lists: Vec<Vec<u8>>,
+        #[serde(with = "super")]
+        credentials: VecDeque<[u8; 4]>,
+        #[serde(with = "super")]
+        hashes: SmallVec<[[u8; 32]; 4]>,
+        #[serde(with = "super::array")]
+        fixed: [[u8; 32]; 2],
+    }
+
+    fn obj() -> Obj {
+        Obj {
+            lists: vec![vec![], vec![0, 1]],
+            credentials: VecDeque::from(vec![[1, 2, 3, 4]]),
+            hashes: SmallVec::from_slice(&[[0xab; 32]]),
+            fixed: [[0; 32], [0xff; 32]],
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let obj = obj();
+        let json = serde_json::to_string(&obj).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"lists":["0x","0x0001"],"credentials":["0x01020304"],"hashes":["0x{}"],"fixed":["0x{}","0x{}"]}}"#,
+                "ab".repeat(32),
+                "00".repeat(32),
+                "ff".repeat(32),
+            )
+        );
+        assert_eq!(serde_json::from_str::<Obj>(&json).unwrap(), obj);
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct Hashes {
+        #[serde(with = "super")]
+        values: Vec<[u8; 32]>,
+    }
+
+    #[test]
+    fn errors() {
+        let hash = format!("\"0x{}\"", "00".repeat(32));
+
+        let err = serde_json::from_str::<Hashes>(&format!("[{}, \"0x00\"]", hash)).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("element 1: expected 32 bytes, got 1"));
+
+        serde_json::from_str::<Hashes>(&format!("[{}, {}]", hash, &hash[3..])).unwrap_err();
+        serde_json::from_str::<Hashes>(&hash).unwrap_err();
+        serde_json::from_str::<Obj>(&serde_json::to_string(&obj()).unwrap().replace(
+            &format!(r#""fixed":["0x{}","#, "00".repeat(32)),
+            r#""fixed":["#,
+        ))
+        .unwrap_err();
+    }
+
+    #[test]
+    fn binary() {
+        let obj = obj();
+        let bytes = bincode::serialize(&obj).unwrap();
+        assert_eq!(bincode::deserialize::<Obj>(&bytes).unwrap(), obj);
+
+        let hashes = Hashes {
+            values: vec![[0xab; 32]],
+        };
+        let bytes = bincode::serialize(&hashes).unwrap();
+        assert_eq!(bytes.len(), 8 + 8 + 32);
+        assert_eq!(bincode::deserialize::<Hashes>(&bytes).unwrap(), hashes);
+    }
+
+    #[cfg(feature = "alloy")]
+    #[test]
+    fn alloy() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Alloy {
+            #[serde(with = "super")]
+            hashes: Vec<B256>,
+            #[serde(with = "super")]
+            addresses: SmallVec<[Address; 4]>,
+            #[serde(with = "super")]
+            data: Vec<Bytes>,
+            #[serde(with = "super::array")]
+            fixed: [B256; 2],
+        }
+
+        let alloy = Alloy {
+            hashes: vec![B256::repeat_byte(0xab)],
+            addresses: SmallVec::from_slice(&[Address::repeat_byte(0x01), Address::ZERO]),
+            data: vec![Bytes::from_static(&[0xde, 0xad])],
+            fixed: [B256::ZERO, B256::repeat_byte(0xff)],
+        };
+        let json = serde_json::to_string(&alloy).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"hashes":["0x{}"],"addresses":["0x{}","0x{}"],"data":["0xdead"],"fixed":["0x{}","0x{}"]}}"#,
+                "ab".repeat(32),
+                "01".repeat(20),
+                "00".repeat(20),
+                "00".repeat(32),
+                "ff".repeat(32),
+            )
+        );
+        assert_eq!(serde_json::from_str::<Alloy>(&json).unwrap(), alloy);
+
+        let err = serde_json::from_str::<Alloy>(&json.replace("0xdead", "0xdea")).unwrap_err();
+        assert!(err.to_string().starts_with("element 0: "));
+
+        let bytes = bincode::serialize(&alloy).unwrap();
+        assert_eq!(bincode::deserialize::<Alloy>(&bytes).unwrap(), alloy);
+    }
+}
@@ -1,29 +1,209 @@
 //! Formats `Vec<u8>` as a 0x-prefixed hex string.
 //!
 //! E.g., `vec![0, 1, 2, 3]` serializes as `"0x00010203"`.
+//!
+//! Binary (non human-readable) formats store the raw bytes instead.
 
-use crate::hex::PrefixedHexVisitor;
+use crate::hex::{serialize_prefixed, BoundedPrefixedHexVisitor, PrefixedHexVisitor};
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
 use serde::{Deserializer, Serializer};
+use serde_derive::{Deserialize, Serialize};
 
 pub fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    let mut hex_string: String = "0x".to_string();
-    hex_string.push_str(&hex::encode(bytes));
+    if !serializer.is_human_readable() {
+        return serializer.serialize_bytes(bytes);
+    }
 
-    serializer.serialize_str(&hex_string)
+    serialize_prefixed(bytes, serializer)
 }
 
 pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    deserializer.deserialize_str(PrefixedHexVisitor)
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(PrefixedHexVisitor)
+    } else {
+        deserializer.deserialize_byte_buf(PrefixedHexVisitor)
+    }
+}
+
+/// Deserialize at most `MAX` bytes, rejecting longer strings before decoding them.
+///
+/// Usage: `#[serde(serialize_with = "hex_vec::serialize", deserialize_with =
+/// "hex_vec::deserialize_bounded::<_, 1024>")]`, or see `BoundedHexBytes`.
+pub fn deserialize_bounded<'de, D, const MAX: usize>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(BoundedPrefixedHexVisitor::<MAX>)
+    } else {
+        deserializer.deserialize_byte_buf(BoundedPrefixedHexVisitor::<MAX>)
+    }
+}
+
+/// The JSON schema of a 0x-prefixed hex string.
+///
+/// Usage: `#[schemars(schema_with = "hex_vec::schema")]`.
+#[cfg(feature = "schemars")]
+pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    crate::schema::hex_bytes()
+}
+
+/// The JSON schema of a 0x-prefixed hex string of at most `MAX` bytes.
+///
+/// Usage: `#[schemars(schema_with = "hex_vec::schema_bounded::<1024>")]`.
+#[cfg(feature = "schemars")]
+pub fn schema_bounded<const MAX: usize>(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    crate::schema::bounded_hex_bytes(MAX)
+}
+
+/// The OpenAPI schema of a 0x-prefixed hex string.
+///
+/// Usage: `#[schema(schema_with = hex_vec::openapi_schema)]`.
+#[cfg(feature = "utoipa")]
+pub fn openapi_schema() -> utoipa::openapi::schema::Schema {
+    crate::openapi::hex_bytes()
+}
+
+/// The OpenAPI schema of a 0x-prefixed hex string of at most `MAX` bytes.
+///
+/// Usage: `#[schema(schema_with = hex_vec::openapi_schema_bounded::<1024>)]`.
+#[cfg(feature = "utoipa")]
+pub fn openapi_schema_bounded<const MAX: usize>() -> utoipa::openapi::schema::Schema {
+    crate::openapi::bounded_hex_bytes(MAX)
+}
+
+/// Wrapper type for formatting `Vec<u8>` as a 0x-prefixed hex string.
+///
+/// Unlike using `serde(with = "hex_vec")` this is composable, and can be nested inside types like
+/// `Option` and `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct HexBytes {
+    #[serde(with = "self")]
+    pub value: Vec<u8>,
+}
+
+impl Deref for HexBytes {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl DerefMut for HexBytes {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl From<Vec<u8>> for HexBytes {
+    fn from(value: Vec<u8>) -> Self {
+        Self { value }
+    }
+}
+
+impl From<HexBytes> for Vec<u8> {
+    fn from(wrapper: HexBytes) -> Self {
+        wrapper.value
+    }
+}
+
+#[cfg(feature = "schemars")]
+crate::schema::impl_json_schema!("HexBytes", [] HexBytes, crate::schema::hex_bytes());
+
+#[cfg(feature = "utoipa")]
+crate::openapi::impl_to_schema!("HexBytes", [] HexBytes, crate::openapi::hex_bytes());
+
+/// Wrapper type for formatting `Vec<u8>` as a 0x-prefixed hex string of at most `MAX` bytes,
+/// e.g. to match the limit of an SSZ `VariableList`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct BoundedHexBytes<const MAX: usize> {
+    #[serde(
+        serialize_with = "serialize",
+        deserialize_with = "deserialize_bounded::<_, MAX>"
+    )]
+    pub value: Vec<u8>,
+}
+
+impl<const MAX: usize> Deref for BoundedHexBytes<MAX> {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<const MAX: usize> DerefMut for BoundedHexBytes<MAX> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<const MAX: usize> From<BoundedHexBytes<MAX>> for Vec<u8> {
+    fn from(wrapper: BoundedHexBytes<MAX>) -> Self {
+        wrapper.value
+    }
+}
+
+#[cfg(feature = "schemars")]
+crate::schema::impl_json_schema!(
+    alloc::format!("BoundedHexBytes{}", MAX),
+    [const MAX: usize] BoundedHexBytes<MAX>,
+    crate::schema::bounded_hex_bytes(MAX)
+);
+
+#[cfg(feature = "utoipa")]
+crate::openapi::impl_to_schema!(
+    alloc::format!("BoundedHexBytes{}", MAX),
+    [const MAX: usize] BoundedHexBytes<MAX>,
+    crate::openapi::bounded_hex_bytes(MAX)
+);
+
+/// Serializes a borrowed byte slice in the same way as `HexBytes`, without copying it.
+#[derive(Serialize)]
+#[serde(transparent)]
+pub(crate) struct BorrowedHexBytes<'a>(#[serde(with = "self")] pub &'a [u8]);
+
+/// Formats `Option<Vec<u8>>` as a 0x-prefixed hex string or `null`.
+///
+/// Usage: `#[serde(with = "hex_vec::option", default)]`, where `default` allows the field to be
+/// omitted entirely.
+pub mod option {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    pub fn serialize<S>(value: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        value.as_deref().map(BorrowedHexBytes).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<HexBytes>::deserialize(deserializer)?.map(Vec::from))
+    }
+
+    #[cfg(feature = "schemars")]
+    pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::schema::nullable(crate::schema::hex_bytes())
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use super::HexBytes;
     use serde::{Deserialize, Serialize};
 
     #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -69,4 +249,87 @@ mod test {
             },
         );
     }
+
+    #[test]
+    fn wrapper_composes() {
+        let values = vec![HexBytes::from(vec![]), HexBytes::from(vec![1, 2])];
+        let json = serde_json::to_string(&values).unwrap();
+        assert_eq!(json, "[\"0x\",\"0x0102\"]");
+        assert_eq!(
+            serde_json::from_str::<Vec<HexBytes>>(&json).unwrap(),
+            values
+        );
+        assert_eq!(values[1].len(), 2);
+
+        assert_eq!(
+            serde_json::from_str::<Option<HexBytes>>("\"0x00\"").unwrap(),
+            Some(HexBytes::from(vec![0]))
+        );
+        serde_json::from_str::<Option<HexBytes>>("\"00\"").unwrap_err();
+    }
+
+    crate::test_utils::option_wrapper!(Vec<u8>);
+
+    #[test]
+    fn option() {
+        crate::test_utils::check_option(
+            OptionWrapper {
+                val: Some(vec![0, 1]),
+            },
+            r#""0x0001""#,
+            &[r#""0001""#],
+        );
+    }
+
+    #[test]
+    fn binary() {
+        let wrapper = Wrapper { val: vec![0, 1, 2] };
+        let bytes = bincode::serialize(&wrapper).unwrap();
+        assert_eq!(bytes, [3, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2]);
+        assert_eq!(bincode::deserialize::<Wrapper>(&bytes).unwrap(), wrapper);
+
+        let some = OptionWrapper {
+            val: Some(vec![0, 1]),
+        };
+        let bytes = bincode::serialize(&some).unwrap();
+        assert_eq!(bincode::deserialize::<OptionWrapper>(&bytes).unwrap(), some);
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Bounded {
+        #[serde(
+            serialize_with = "super::serialize",
+            deserialize_with = "super::deserialize_bounded::<_, 2>"
+        )]
+        val: Vec<u8>,
+        wrapped: Option<super::BoundedHexBytes<2>>,
+    }
+
+    #[test]
+    fn bounded() {
+        let bounded: Bounded =
+            serde_json::from_str(r#"{"val":"0x0001","wrapped":"0x02"}"#).unwrap();
+        assert_eq!(bounded.val, vec![0, 1]);
+        assert_eq!(bounded.wrapped.as_deref(), Some(&vec![2]));
+
+        let err =
+            serde_json::from_str::<Bounded>(r#"{"val":"0x000102","wrapped":null}"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("3 bytes exceeds the maximum length of 2 bytes"));
+        serde_json::from_str::<Bounded>(r#"{"val":"0x","wrapped":"0x000102"}"#).unwrap_err();
+
+        let too_long = bincode::serialize(&Wrapper { val: vec![0; 3] }).unwrap();
+        let err = bincode::deserialize::<super::BoundedHexBytes<2>>(&too_long).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("exceeds the maximum length of 2 bytes"));
+        let ok = bincode::serialize(&Wrapper { val: vec![0; 2] }).unwrap();
+        assert_eq!(
+            bincode::deserialize::<super::BoundedHexBytes<2>>(&ok)
+                .unwrap()
+                .value,
+            vec![0; 2]
+        );
+    }
 }
@@ -1,4 +1,5 @@
 //! Serialize a datatype as a JSON-blob within a single string.
+use alloc::string::String;
 use serde::{
     de::{DeserializeOwned, Error as _},
     ser::Error as _,
@@ -24,6 +25,17 @@ where
     serde_json::from_str(&json_str).map_err(D::Error::custom)
 }
 
+/// The JSON schema of a string containing JSON.
+///
+/// Usage: `#[schemars(schema_with = "json_str::schema")]`.
+#[cfg(feature = "schemars")]
+pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    schemars::json_schema!({
+        "type": "string",
+        "contentMediaType": "application/json",
+    })
+}
+
 #[cfg(test)]
 mod test {
     use serde::{Deserialize, Serialize};
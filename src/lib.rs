@@ -1,18 +1,63 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+mod fixed_int_hex;
+mod hex_decode;
+#[cfg(feature = "utoipa")]
+mod openapi;
+#[cfg(any(feature = "schemars", feature = "utoipa"))]
+mod patterns;
+mod preview;
 mod quoted_int;
+#[cfg(feature = "schemars")]
+mod schema;
+mod seq;
+#[cfg(test)]
+mod test_utils;
+mod uint256;
+
+/// The ruint crate, either as a direct dependency or as re-exported by alloy. Both resolve to the
+/// same crate, so the `Uint` types and trait implementations are shared.
+#[cfg(feature = "ruint")]
+use ::ruint;
+#[cfg(all(feature = "alloy", not(feature = "ruint")))]
+use alloy_primitives::ruint;
 
+#[cfg(feature = "alloy")]
 pub mod address_hex;
+#[cfg(feature = "alloy")]
 pub mod b256_hex;
+pub mod blob_hex;
 pub mod fixed_bytes_hex;
 pub mod hex;
+pub mod hex_seq;
 pub mod hex_vec;
+#[cfg(feature = "json_str")]
 pub mod json_str;
 pub mod list_of_bytes_lists;
+pub mod quantity;
+pub mod quoted_seq;
 pub mod quoted_u64_vec;
+#[cfg(feature = "serde_with")]
+pub mod serde_as;
 pub mod u256_dec;
 pub mod u256_hex_be;
 pub mod u32_hex;
 pub mod u64_hex_be;
 pub mod u8_hex;
 
-pub use fixed_bytes_hex::{bytes_4_hex, bytes_8_hex};
-pub use quoted_int::{quoted_i64, quoted_u256, quoted_u32, quoted_u64, quoted_u8};
+#[cfg(feature = "alloy")]
+pub use address_hex::{ChecksummedAddress, HexAddress};
+#[cfg(feature = "alloy")]
+pub use b256_hex::HexB256;
+pub use blob_hex::HexBlob;
+pub use fixed_bytes_hex::{bytes_4_hex, bytes_8_hex, HexArray};
+pub use fixed_int_hex::{u16_hex_be_fixed, u32_hex_be_fixed, u64_hex_be_fixed};
+pub use hex_seq::HexItem;
+pub use hex_vec::{BoundedHexBytes, HexBytes};
+pub use quoted_int::{
+    quoted_i128, quoted_i32, quoted_i64, quoted_u128, quoted_u16, quoted_u256, quoted_u32,
+    quoted_u64, quoted_u8, quoted_usize, QuotedInt,
+};
+pub use uint256::Uint256;
@@ -1,18 +1,49 @@
-//! Formats `Vec<u64>` using quotes.
+//! Formats `Vec<Vec<u8>>` as a list of 0x-prefixed hex strings.
 //!
-//! E.g., `vec![0, 1, 2]` serializes as `["0", "1", "2"]`.
+//! E.g., `vec![vec![0], vec![1, 2]]` serializes as `["0x00", "0x0102"]`.
 //!
-//! Quotes can be optional during decoding.
+//! `deserialize_bounded` limits both the number of lists and the length of each list.
 
-use crate::hex;
-use serde::ser::SerializeSeq;
-use serde::{de, Deserializer, Serializer};
+use crate::hex::{self, HexError};
+use crate::hex_vec::{BorrowedHexBytes, BoundedHexBytes};
+use crate::preview::Preview;
+use alloc::{format, string::String, vec, vec::Vec};
+use core::fmt;
+use serde::de::{DeserializeSeed, IgnoredAny, SeqAccess, Visitor};
+use serde::{de, Deserialize, Deserializer, Serializer};
+
+/// Deserializes the byte list at `index` within a list, of at most `MAX` bytes, such that errors
+/// identify the element.
+struct Element<const MAX: usize> {
+    index: usize,
+}
+
+impl<'de, const MAX: usize> DeserializeSeed<'de> for Element<MAX> {
+    type Value = Vec<u8>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return BoundedHexBytes::<MAX>::deserialize(deserializer)
+                .map(Vec::from)
+                .map_err(|e| de::Error::custom(format!("element {}: {}", self.index, e)));
+        }
+
+        let val = String::deserialize(deserializer)
+            .map_err(|e| de::Error::custom(format!("element {}: {}", self.index, e)))?;
+        hex::decode_bounded(&val, MAX).map_err(|e| {
+            de::Error::custom(format!("element {} ({}): {}", self.index, Preview(&val), e))
+        })
+    }
+}
 
 pub struct ListOfBytesListVisitor;
 impl<'a> serde::de::Visitor<'a> for ListOfBytesListVisitor {
     type Value = Vec<Vec<u8>>;
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(formatter, "a list of 0x-prefixed byte lists")
     }
 
@@ -22,10 +53,60 @@ impl<'a> serde::de::Visitor<'a> for ListOfBytesListVisitor {
     {
         let mut vec = vec![];
 
-        while let Some(val) = seq.next_element::<String>()? {
-            vec.push(hex::decode(&val).map_err(de::Error::custom)?);
+        while let Some(bytes) =
+            seq.next_element_seed(Element::<{ usize::MAX }> { index: vec.len() })?
+        {
+            vec.push(bytes);
+        }
+
+        Ok(vec)
+    }
+}
+
+/// Deserializes at most `MAX_LISTS` byte lists, each of at most `MAX_BYTES` bytes.
+///
+/// Excess lists are rejected without being decoded.
+pub struct BoundedListOfBytesListVisitor<const MAX_LISTS: usize, const MAX_BYTES: usize>;
+
+impl<'de, const MAX_LISTS: usize, const MAX_BYTES: usize> Visitor<'de>
+    for BoundedListOfBytesListVisitor<MAX_LISTS, MAX_BYTES>
+{
+    type Value = Vec<Vec<u8>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "a list of at most {} 0x-prefixed byte lists of at most {} bytes",
+            MAX_LISTS, MAX_BYTES
+        )
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let too_many = || {
+            de::Error::custom(HexError::TooManyLists {
+                max: MAX_LISTS,
+                got: None,
+            })
+        };
+
+        let hint = seq.size_hint().unwrap_or(0);
+        if hint > MAX_LISTS {
+            return Err(too_many());
         }
+        let mut vec = Vec::with_capacity(hint.min(4096));
 
+        while vec.len() < MAX_LISTS {
+            match seq.next_element_seed(Element::<MAX_BYTES> { index: vec.len() })? {
+                Some(bytes) => vec.push(bytes),
+                None => return Ok(vec),
+            }
+        }
+        if seq.next_element::<IgnoredAny>()?.is_some() {
+            return Err(too_many());
+        }
         Ok(vec)
     }
 }
@@ -34,20 +115,53 @@ pub fn serialize<S>(value: &[Vec<u8>], serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    let mut seq = serializer.serialize_seq(Some(value.len()))?;
-    for val in value {
-        seq.serialize_element(&hex::encode(val))?;
-    }
-    seq.end()
+    serializer.collect_seq(value.iter().map(|val| BorrowedHexBytes(val)))
 }
 
 pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Vec<u8>>, D::Error>
 where
     D: Deserializer<'de>,
 {
+    if !deserializer.is_human_readable() {
+        return deserializer.deserialize_seq(ListOfBytesListVisitor);
+    }
+
     deserializer.deserialize_any(ListOfBytesListVisitor)
 }
 
+/// The JSON schema of a list of 0x-prefixed hex strings.
+///
+/// Usage: `#[schemars(schema_with = "list_of_bytes_lists::schema")]`.
+#[cfg(feature = "schemars")]
+pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    crate::schema::array(crate::schema::hex_bytes(), None)
+}
+
+/// Deserialize at most `MAX_LISTS` byte lists, each of at most `MAX_BYTES` bytes, e.g. to match
+/// the limits of an SSZ `VariableList<VariableList<u8, MAX_BYTES>, MAX_LISTS>`.
+///
+/// Usage: `#[serde(serialize_with = "list_of_bytes_lists::serialize", deserialize_with =
+/// "list_of_bytes_lists::deserialize_bounded::<_, 1048576, 1073741824>")]`.
+pub fn deserialize_bounded<'de, D, const MAX_LISTS: usize, const MAX_BYTES: usize>(
+    deserializer: D,
+) -> Result<Vec<Vec<u8>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_seq(BoundedListOfBytesListVisitor::<MAX_LISTS, MAX_BYTES>)
+}
+
+/// The JSON schema matching `deserialize_bounded`.
+///
+/// Usage: `#[schemars(schema_with =
+/// "list_of_bytes_lists::schema_bounded::<1048576, 1073741824>")]`.
+#[cfg(feature = "schemars")]
+pub fn schema_bounded<const MAX_LISTS: usize, const MAX_BYTES: usize>(
+    _: &mut schemars::SchemaGenerator,
+) -> schemars::Schema {
+    crate::schema::bounded_array(crate::schema::bounded_hex_bytes(MAX_BYTES), MAX_LISTS)
+}
+
 #[cfg(test)]
 mod test {
     use serde::{Deserialize, Serialize};
@@ -100,4 +214,99 @@ mod test {
             },
         );
     }
+
+    #[test]
+    fn binary() {
+        let wrapper = Wrapper {
+            val: vec![vec![], vec![0, 1, 2]],
+        };
+        let bytes = bincode::serialize(&wrapper).unwrap();
+        assert_eq!(bytes.len(), 8 + 8 + 8 + 3);
+        assert_eq!(bincode::deserialize::<Wrapper>(&bytes).unwrap(), wrapper);
+
+        // A truncated element.
+        let err = bincode::deserialize::<Wrapper>(&bytes[..bytes.len() - 1]).unwrap_err();
+        assert!(err.to_string().starts_with("element 1: "), "{}", err);
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct Bounded {
+        #[serde(
+            serialize_with = "super::serialize",
+            deserialize_with = "super::deserialize_bounded::<_, 2, 3>"
+        )]
+        val: Vec<Vec<u8>>,
+    }
+
+    #[test]
+    fn bounded() {
+        assert_eq!(
+            serde_json::from_str::<Bounded>(r#"["0x000102", "0x"]"#).unwrap(),
+            Bounded {
+                val: vec![vec![0, 1, 2], vec![]]
+            }
+        );
+
+        // Too many lists, where the excess list is not decoded.
+        let err = serde_json::from_str::<Bounded>(r#"["0x00", "0x01", "not hex"]"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("list exceeds the maximum length of 2 byte lists"));
+
+        // A list which is too long.
+        let err = serde_json::from_str::<Bounded>(r#"["0x00", "0x00010203"]"#).unwrap_err();
+        assert!(err.to_string().starts_with(
+            r#"element 1 ("0x00010203"): 4 bytes exceeds the maximum length of 3 bytes"#
+        ));
+
+        let bounded = Bounded {
+            val: vec![vec![0, 1, 2], vec![]],
+        };
+        let bytes = bincode::serialize(&bounded).unwrap();
+        assert_eq!(bincode::deserialize::<Bounded>(&bytes).unwrap(), bounded);
+        let too_many = bincode::serialize(&Wrapper {
+            val: vec![vec![]; 3],
+        })
+        .unwrap();
+        let err = bincode::deserialize::<Bounded>(&too_many).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("list exceeds the maximum length of 2 byte lists"));
+        let too_long = bincode::serialize(&Wrapper {
+            val: vec![vec![0; 4]],
+        })
+        .unwrap();
+        let err = bincode::deserialize::<Bounded>(&too_long).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "element 0: 4 bytes exceeds the maximum length of 3 bytes"
+        );
+    }
+
+    #[test]
+    fn element_type() {
+        let err = serde_json::from_str::<Wrapper>(r#"["0x00", 5]"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("element 1: invalid type: integer `5`, expected a string"));
+        let err = serde_json::from_str::<Bounded>(r#"["0x00", 5]"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("element 1: invalid type: integer `5`, expected a string"));
+    }
+
+    #[test]
+    fn error_position() {
+        let json = format!(r#"["0x00", "0x01", "0x{}zz"]"#, "ab".repeat(100));
+        let err = serde_json::from_str::<Wrapper>(&json).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                r#"element 2 ("0x{}"...): invalid hex character 'z' at index 202 at line 1 column {}"#,
+                "ab".repeat(9),
+                json.len()
+            )
+        );
+    }
 }
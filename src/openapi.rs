@@ -0,0 +1,229 @@
+//! OpenAPI schemas describing the wire format of the quoted and hex types, for use with `utoipa`.
+//!
+//! Each supported module provides an `openapi_schema` function for
+//! `#[schema(schema_with = ...)]`, and its wrapper types implement `ToSchema` for
+//! `#[schema(value_type = ...)]`.
+
+use crate::patterns::{hex_len, HEX_PATTERN, INT_PATTERN, UINT_PATTERN};
+use alloc::format;
+use utoipa::openapi::schema::{ObjectBuilder, Schema, SchemaFormat, Type};
+
+/// A string with the given `format`, `pattern` and `example`.
+fn string(format: &str, pattern: &str, example: &str) -> ObjectBuilder {
+    ObjectBuilder::new()
+        .schema_type(Type::String)
+        .format(Some(SchemaFormat::Custom(format.into())))
+        .pattern(Some(pattern))
+        .examples([example])
+}
+
+/// A 0x-prefixed hex string of any number of bytes.
+pub(crate) fn hex_bytes() -> Schema {
+    string("hex", HEX_PATTERN, "0xdeadbeef").build().into()
+}
+
+/// A 0x-prefixed hex string of at most `max` bytes.
+pub(crate) fn bounded_hex_bytes(max: usize) -> Schema {
+    // The example is truncated to fit within the maximum length.
+    let example = &"0xdeadbeef"[..hex_len(max.min(4))];
+    string("hex", HEX_PATTERN, example)
+        .max_length(Some(hex_len(max)))
+        .build()
+        .into()
+}
+
+/// A 0x-prefixed hex string of exactly `len` bytes.
+#[cfg(feature = "alloy")]
+fn fixed_hex_bytes(len: usize, example: &str) -> Schema {
+    string("hex", HEX_PATTERN, example)
+        .min_length(Some(hex_len(len)))
+        .max_length(Some(hex_len(len)))
+        .build()
+        .into()
+}
+
+/// A 0x-prefixed hex string of a 32-byte hash.
+#[cfg(feature = "alloy")]
+pub(crate) fn b256() -> Schema {
+    fixed_hex_bytes(
+        32,
+        "0xcf8e0d4e9587369b2301d0790347320302cc0943d5a1884560367e8208d920f2",
+    )
+}
+
+/// A 0x-prefixed hex string of a 20-byte address.
+#[cfg(feature = "alloy")]
+pub(crate) fn address() -> Schema {
+    fixed_hex_bytes(20, "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")
+}
+
+/// A 0x-prefixed address with an EIP-55 mixed-case checksum.
+#[cfg(feature = "alloy")]
+pub(crate) fn checksummed_address() -> Schema {
+    string(
+        "address",
+        crate::patterns::CHECKSUMMED_ADDRESS_PATTERN,
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+    )
+    .build()
+    .into()
+}
+
+/// A quoted decimal integer of `bits` bits, e.g. with the format `uint64` or `int32`.
+pub(crate) fn quoted_int(signed: bool, bits: u32) -> Schema {
+    let (format, pattern) = if signed {
+        (format!("int{}", bits), INT_PATTERN)
+    } else {
+        (format!("uint{}", bits), UINT_PATTERN)
+    };
+    string(&format, pattern, "1").build().into()
+}
+
+/// Implement `ToSchema` for a wrapper type, named `$name` in the components of an OpenAPI document.
+///
+/// Usage: `impl_to_schema!("HexB256", [] HexB256, b256())`.
+macro_rules! impl_to_schema {
+    ($name: expr, [$($generics: tt)*] $ty: ty, $schema: expr) => {
+        impl<$($generics)*> utoipa::PartialSchema for $ty {
+            fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+                $schema.into()
+            }
+        }
+
+        impl<$($generics)*> utoipa::ToSchema for $ty {
+            fn name() -> alloc::borrow::Cow<'static, str> {
+                $name.into()
+            }
+        }
+    };
+}
+
+pub(crate) use impl_to_schema;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::json;
+    use utoipa::{PartialSchema, ToSchema};
+
+    /// The example of `T` matches the wire format of its module.
+    fn round_trip<T: PartialSchema + Serialize + DeserializeOwned>() {
+        let example = serde_json::to_value(T::schema()).unwrap()["examples"][0].to_string();
+        let value = serde_json::from_str::<T>(&example).unwrap();
+        assert_eq!(serde_json::to_string(&value).unwrap(), example);
+    }
+
+    #[test]
+    fn patterns() {
+        assert_eq!(
+            serde_json::to_value(bounded_hex_bytes(4)).unwrap(),
+            json!({
+                "type": "string",
+                "format": "hex",
+                "pattern": "^0x[0-9a-f]*$",
+                "maxLength": 10,
+                "examples": ["0xdeadbeef"],
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(quoted_int(false, 64)).unwrap(),
+            json!({
+                "type": "string",
+                "format": "uint64",
+                "pattern": "^(0|[1-9][0-9]*)$",
+                "examples": ["1"],
+            })
+        );
+    }
+
+    #[test]
+    fn examples() {
+        round_trip::<crate::HexBytes>();
+        round_trip::<crate::BoundedHexBytes<0>>();
+        round_trip::<crate::BoundedHexBytes<1>>();
+        round_trip::<crate::BoundedHexBytes<4>>();
+        round_trip::<crate::BoundedHexBytes<32>>();
+        round_trip::<crate::quoted_u64::Quoted<u64>>();
+        round_trip::<crate::quoted_i32::MaybeQuoted<i32>>();
+
+        assert_eq!(crate::quoted_i32::Quoted::<i32>::name(), "QuotedI32");
+        assert_eq!(crate::BoundedHexBytes::<32>::name(), "BoundedHexBytes32");
+    }
+
+    #[cfg(any(feature = "alloy", feature = "ruint"))]
+    #[test]
+    fn u256_examples() {
+        round_trip::<crate::quoted_u256::Quoted<crate::ruint::aliases::U256>>();
+        assert_eq!(
+            crate::quoted_u256::Quoted::<crate::ruint::aliases::U256>::name(),
+            "QuotedU256"
+        );
+    }
+
+    #[cfg(feature = "alloy")]
+    #[test]
+    fn alloy_examples() {
+        round_trip::<crate::HexB256>();
+        round_trip::<crate::HexAddress>();
+        round_trip::<crate::ChecksummedAddress>();
+
+        // Fixed lengths are given by the lengths rather than the pattern.
+        assert_eq!(
+            serde_json::to_value(fixed_hex_bytes(4, "0x01020304")).unwrap(),
+            json!({
+                "type": "string",
+                "format": "hex",
+                "pattern": "^0x[0-9a-f]*$",
+                "minLength": 10,
+                "maxLength": 10,
+                "examples": ["0x01020304"],
+            })
+        );
+    }
+
+    /// The pattern and lengths of each string match its JSON schema.
+    #[cfg(all(feature = "alloy", feature = "schemars"))]
+    #[test]
+    fn json_schema() {
+        let pairs = [
+            (b256(), crate::schema::fixed_hex_bytes(32)),
+            (address(), crate::schema::fixed_hex_bytes(20)),
+            (bounded_hex_bytes(4), crate::schema::bounded_hex_bytes(4)),
+            (checksummed_address(), crate::schema::checksummed_address()),
+        ];
+        for (openapi, json_schema) in pairs {
+            let openapi = serde_json::to_value(openapi).unwrap();
+            for key in ["pattern", "minLength", "maxLength"] {
+                assert_eq!(openapi.get(key), json_schema.get(key), "{}", key);
+            }
+        }
+    }
+
+    #[cfg(feature = "alloy")]
+    #[allow(dead_code)]
+    #[derive(utoipa::ToSchema)]
+    struct Validator {
+        #[schema(schema_with = crate::quoted_u64::openapi_schema)]
+        index: u64,
+        #[schema(value_type = crate::HexB256)]
+        root: alloy_primitives::B256,
+        #[schema(value_type = Option<crate::HexBytes>)]
+        data: Option<Vec<u8>>,
+    }
+
+    #[cfg(feature = "alloy")]
+    #[test]
+    fn derive() {
+        let schema = serde_json::to_value(Validator::schema()).unwrap();
+        assert_eq!(
+            schema["properties"]["index"],
+            serde_json::to_value(quoted_int(false, 64)).unwrap()
+        );
+        assert_eq!(
+            schema["properties"]["root"],
+            json!({ "$ref": "#/components/schemas/HexB256" })
+        );
+        assert_eq!(schema["required"], json!(["index", "root"]));
+    }
+}
@@ -0,0 +1,22 @@
+//! The patterns and lengths of the string formats, shared by the `schemars` and `utoipa` schemas.
+//!
+//! Fixed-length hex strings use `HEX_PATTERN` with equal minimum and maximum lengths, rather than
+//! a pattern of their own.
+
+/// The pattern of every 0x-prefixed hex string of bytes, as produced by `hex::encode`.
+pub(crate) const HEX_PATTERN: &str = "^0x[0-9a-f]*$";
+
+/// The pattern of a 0x-prefixed address with an EIP-55 mixed-case checksum.
+#[cfg(feature = "alloy")]
+pub(crate) const CHECKSUMMED_ADDRESS_PATTERN: &str = "^0x[0-9a-fA-F]{40}$";
+
+/// The pattern of an unsigned decimal integer.
+pub(crate) const UINT_PATTERN: &str = "^(0|[1-9][0-9]*)$";
+
+/// The pattern of a signed decimal integer.
+pub(crate) const INT_PATTERN: &str = "^-?(0|[1-9][0-9]*)$";
+
+/// The length of a 0x-prefixed hex string encoding `bytes` bytes.
+pub(crate) const fn hex_len(bytes: usize) -> usize {
+    2 + 2 * bytes
+}
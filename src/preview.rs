@@ -0,0 +1,38 @@
+//! Previews of input values for error messages.
+
+use core::fmt;
+
+/// The number of characters of the value to include.
+const MAX_CHARS: usize = 20;
+
+/// Formats a value from the input, which may be very long (e.g. a transaction), as a quoted and
+/// escaped string truncated to `MAX_CHARS` characters.
+pub(crate) struct Preview<'a>(pub &'a str);
+
+impl fmt::Display for Preview<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0.char_indices().nth(MAX_CHARS) {
+            Some((end, _)) => write!(f, "{:?}...", &self.0[..end]),
+            None => write!(f, "{:?}", self.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn truncation() {
+        assert_eq!(Preview("0x00").to_string(), r#""0x00""#);
+        assert_eq!(
+            Preview(&"a".repeat(MAX_CHARS)).to_string(),
+            format!("\"{}\"", "a".repeat(MAX_CHARS))
+        );
+        assert_eq!(
+            Preview(&"é".repeat(100)).to_string(),
+            format!("\"{}\"...", "é".repeat(MAX_CHARS))
+        );
+        assert_eq!(Preview("\"\n").to_string(), r#""\"\n""#);
+    }
+}
@@ -0,0 +1,419 @@
+//! Formats unsigned integers as JSON-RPC "quantities": 0x-prefixed, big-endian hex strings with
+//! no leading zeros.
+//!
+//! E.g., `0` serializes as `"0x0"` and `1024` serializes as `"0x400"`.
+//!
+//! Works for all of the primitive unsigned integers, for ruint's `Uint<BITS, LIMBS>` (`U64`,
+//! `U128`, `U256`, etc., also re-exported by alloy) and for primitive-types' `U128`, `U256` and
+//! `U512`, so that every quantity is validated by the same rules.
+//!
+//! Binary (non human-readable) formats use native integers for primitives and big-endian bytes for
+//! the big integer types.
+
+use crate::hex::HexError;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// An unsigned integer which can be formatted as a quantity.
+pub trait Quantity: Copy + fmt::LowerHex {
+    /// The width of the integer, used to report overflows.
+    const BITS: usize;
+
+    /// Parse from big-endian hex digits which have already been validated.
+    ///
+    /// Returns `None` if the value does not fit in `Self`.
+    fn from_hex_digits(digits: &str) -> Option<Self>;
+
+    /// Serialize for binary (non human-readable) formats.
+    fn serialize_binary<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+
+    /// Deserialize for binary (non human-readable) formats.
+    fn deserialize_binary<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+macro_rules! impl_quantity {
+    ($($int: ty),*) => {
+        $(
+            impl Quantity for $int {
+                const BITS: usize = <$int>::BITS as usize;
+
+                fn from_hex_digits(digits: &str) -> Option<Self> {
+                    <$int>::from_str_radix(digits, 16).ok()
+                }
+
+                fn serialize_binary<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    self.serialize(serializer)
+                }
+
+                fn deserialize_binary<'de, D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    Self::deserialize(deserializer)
+                }
+            }
+        )*
+    };
+}
+
+impl_quantity!(u8, u16, u32, u64, u128, usize);
+
+#[cfg(any(feature = "alloy", feature = "ruint"))]
+impl<const BITS: usize, const LIMBS: usize> Quantity for crate::ruint::Uint<BITS, LIMBS> {
+    const BITS: usize = BITS;
+
+    fn from_hex_digits(digits: &str) -> Option<Self> {
+        Self::from_str_radix(digits, 16).ok()
+    }
+
+    fn serialize_binary<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.serialize(serializer)
+    }
+
+    fn deserialize_binary<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::deserialize(deserializer)
+    }
+}
+
+/// Binary formats use the same big-endian bytes as ruint, as primitive-types only implements serde
+/// with its own `serde` feature, which requires `std`.
+#[cfg(feature = "primitive-types")]
+macro_rules! impl_primitive_types_quantity {
+    ($($int: ident, $bytes: expr);*) => {
+        $(
+            impl Quantity for primitive_types::$int {
+                const BITS: usize = 8 * $bytes;
+
+                fn from_hex_digits(digits: &str) -> Option<Self> {
+                    Self::from_str_radix(digits, 16).ok()
+                }
+
+                fn serialize_binary<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    serializer.serialize_bytes(&self.to_big_endian())
+                }
+
+                fn deserialize_binary<'de, D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    crate::fixed_bytes_hex::deserialize::<_, $bytes>(deserializer)
+                        .map(|bytes| Self::from_big_endian(&bytes))
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "primitive-types")]
+impl_primitive_types_quantity!(U128, 16; U256, 32; U512, 64);
+
+/// Decode a quantity from a 0x-prefixed hex string.
+pub fn decode<T: Quantity>(s: &str) -> Result<T, HexError> {
+    let digits = s.strip_prefix("0x").ok_or(HexError::MissingPrefix)?;
+
+    if digits.is_empty() {
+        return Err(HexError::EmptyQuantity);
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        return Err(HexError::LeadingZero);
+    }
+
+    if let Some((index, char)) = digits.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        return Err(HexError::InvalidChar {
+            index: index + 2,
+            char,
+        });
+    }
+
+    let overflow = HexError::Overflow { bits: T::BITS };
+    if digits.len() > T::BITS.div_ceil(4) {
+        return Err(overflow);
+    }
+    T::from_hex_digits(digits).ok_or(overflow)
+}
+
+pub struct QuantityVisitor<T> {
+    _phantom: PhantomData<T>,
+}
+
+impl<T> QuantityVisitor<T> {
+    pub const fn new() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for QuantityVisitor<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Quantity> Visitor<'_> for QuantityVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "a 0x-prefixed hex quantity of at most {} bits",
+            T::BITS
+        )
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        decode(value).map_err(de::Error::custom)
+    }
+}
+
+pub fn serialize<S, T>(num: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Quantity,
+{
+    if !serializer.is_human_readable() {
+        return num.serialize_binary(serializer);
+    }
+
+    serializer.collect_str(&format_args!("0x{:x}", num))
+}
+
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Quantity,
+{
+    if !deserializer.is_human_readable() {
+        return T::deserialize_binary(deserializer);
+    }
+
+    deserializer.deserialize_str(QuantityVisitor::new())
+}
+
+/// The JSON schema of a quantity of type `T`, e.g. at most 16 hex digits for `u64`.
+///
+/// Usage: `#[schemars(schema_with = "quantity::schema::<u64>")]`.
+#[cfg(feature = "schemars")]
+pub fn schema<T: Quantity>(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    crate::schema::quantity(T::BITS)
+}
+
+/// Formats `Option<T>` as a quantity or `null`.
+///
+/// Usage: `#[serde(with = "quantity::option", default)]`, where `default` allows the field to be
+/// omitted entirely.
+pub mod option {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(transparent, bound = "T: Quantity")]
+    struct Wrapper<T>(#[serde(with = "super")] T);
+
+    pub fn serialize<S, T>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Quantity,
+    {
+        value.map(Wrapper).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Quantity,
+    {
+        Ok(Option::<Wrapper<T>>::deserialize(deserializer)?.map(|wrapper| wrapper.0))
+    }
+
+    #[cfg(feature = "schemars")]
+    pub fn schema<T: Quantity>(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::schema::nullable(crate::schema::quantity(T::BITS))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct Wrapper<T: Quantity> {
+        #[serde(with = "super")]
+        val: T,
+    }
+
+    fn round_trip<T: Quantity + PartialEq + fmt::Debug>(val: T, expected: &str) {
+        let wrapper = Wrapper { val };
+        let json = serde_json::to_string(&wrapper).unwrap();
+        assert_eq!(json, format!("\"{}\"", expected));
+        assert_eq!(serde_json::from_str::<Wrapper<T>>(&json).unwrap(), wrapper);
+    }
+
+    #[test]
+    fn encoding_and_decoding() {
+        round_trip(0u8, "0x0");
+        round_trip(u8::MAX, "0xff");
+        round_trip(0x1234u16, "0x1234");
+        round_trip(u32::MAX, "0xffffffff");
+        round_trip(1024u64, "0x400");
+        round_trip(u128::MAX, &format!("0x{}", "f".repeat(32)));
+    }
+
+    #[test]
+    fn decoding_errors() {
+        assert_eq!(decode::<u64>("400"), Err(HexError::MissingPrefix));
+        assert_eq!(decode::<u64>("0x"), Err(HexError::EmptyQuantity));
+        assert_eq!(decode::<u64>("0x0400"), Err(HexError::LeadingZero));
+        assert_eq!(decode::<u64>("0x00"), Err(HexError::LeadingZero));
+        assert_eq!(
+            decode::<u64>("0x4g0"),
+            Err(HexError::InvalidChar {
+                index: 3,
+                char: 'g'
+            })
+        );
+        assert_eq!(
+            decode::<u64>("0x+1"),
+            Err(HexError::InvalidChar {
+                index: 2,
+                char: '+'
+            })
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(decode::<u8>("0x100"), Err(HexError::Overflow { bits: 8 }));
+        assert_eq!(
+            decode::<u16>("0x10000"),
+            Err(HexError::Overflow { bits: 16 })
+        );
+        assert_eq!(
+            decode::<u64>("0x10000000000000000"),
+            Err(HexError::Overflow { bits: 64 })
+        );
+    }
+
+    crate::test_utils::option_wrapper!(u64);
+
+    #[test]
+    fn option() {
+        crate::test_utils::check_option(
+            OptionWrapper { val: Some(1024) },
+            r#""0x400""#,
+            &[r#""0x0400""#],
+        );
+    }
+
+    fn binary_round_trip<T: Quantity + PartialEq + fmt::Debug>(val: T, expected: &[u8]) {
+        let wrapper = Wrapper { val };
+        let bytes = bincode::serialize(&wrapper).unwrap();
+        assert_eq!(bytes, expected);
+        assert_eq!(bincode::deserialize::<Wrapper<T>>(&bytes).unwrap(), wrapper);
+    }
+
+    #[test]
+    fn binary() {
+        binary_round_trip(0xabu8, &[0xab]);
+        binary_round_trip(1024u64, &1024u64.to_le_bytes());
+    }
+
+    #[cfg(any(feature = "alloy", feature = "ruint"))]
+    #[test]
+    fn ruint() {
+        use crate::ruint::aliases::{U128, U256, U64};
+        use crate::ruint::Uint;
+
+        round_trip(U64::from(65), "0x41");
+        round_trip(U128::ZERO, "0x0");
+        round_trip(U256::MAX, &format!("0x{}", "f".repeat(64)));
+
+        assert_eq!(decode::<U256>("0x00"), Err(HexError::LeadingZero));
+        assert_eq!(
+            decode::<U256>("0x+1"),
+            Err(HexError::InvalidChar {
+                index: 2,
+                char: '+'
+            })
+        );
+        assert_eq!(
+            decode::<U64>("0x10000000000000000"),
+            Err(HexError::Overflow { bits: 64 })
+        );
+        assert_eq!(
+            decode::<U256>(&format!("0x1{}", "0".repeat(64))),
+            Err(HexError::Overflow { bits: 256 })
+        );
+        // Overflow within the final digit.
+        assert_eq!(
+            decode::<Uint<7, 1>>("0x80"),
+            Err(HexError::Overflow { bits: 7 })
+        );
+        assert_eq!(
+            decode::<Uint<7, 1>>("0x7f").unwrap(),
+            Uint::<7, 1>::from(0x7f)
+        );
+
+        binary_round_trip(
+            U64::from(1024),
+            &[8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4, 0],
+        );
+        let mut expected = vec![32, 0, 0, 0, 0, 0, 0, 0];
+        expected.extend_from_slice(&[0xff; 32]);
+        binary_round_trip(U256::MAX, &expected);
+    }
+
+    #[cfg(feature = "primitive-types")]
+    #[test]
+    fn primitive_types() {
+        use primitive_types::{U128, U256, U512};
+
+        round_trip(U128::from(65), "0x41");
+        round_trip(U256::zero(), "0x0");
+        round_trip(U256::MAX, &format!("0x{}", "f".repeat(64)));
+        round_trip(U512::MAX, &format!("0x{}", "f".repeat(128)));
+
+        assert_eq!(decode::<U256>("0x00"), Err(HexError::LeadingZero));
+        assert_eq!(
+            decode::<U256>("0x+1"),
+            Err(HexError::InvalidChar {
+                index: 2,
+                char: '+'
+            })
+        );
+        assert_eq!(
+            decode::<U128>(&format!("0x1{}", "0".repeat(32))),
+            Err(HexError::Overflow { bits: 128 })
+        );
+        assert_eq!(
+            decode::<U256>(&format!("0x1{}", "0".repeat(64))),
+            Err(HexError::Overflow { bits: 256 })
+        );
+
+        // The same big-endian bytes as ruint.
+        let mut expected = vec![32, 0, 0, 0, 0, 0, 0, 0];
+        expected.extend_from_slice(&[0; 30]);
+        expected.extend_from_slice(&[4, 0]);
+        binary_round_trip(U256::from(1024), &expected);
+    }
+}
@@ -3,12 +3,56 @@
 //! E.g., `1` serializes as `"1"`.
 //!
 //! Quotes can be optional during decoding.
+//!
+//! Binary (non human-readable) formats store the integer natively, without quotes.
+
+use crate::preview::Preview;
+use alloc::{format, string::String};
+use core::convert::TryFrom;
+use core::marker::PhantomData;
+use serde::de::{MapAccess, Unexpected};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Integer types that can be formatted using quotes.
+///
+/// Implemented for the integer type of every `quoted_*` module, so that generic adapters such as
+/// `quoted_seq` can use the matching encoding for each element.
+pub trait QuotedInt: Sized {
+    /// Serialize with quotes.
+    fn serialize_quoted<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+
+    /// Deserialize with quotes, or without quotes if `require_quotes` is `false`.
+    fn deserialize_quoted<'de, D>(deserializer: D, require_quotes: bool) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+
+    /// The JSON schema of the quoted integer, which may be unquoted unless `require_quotes`.
+    ///
+    /// Defaults to a signed integer of any size.
+    #[cfg(feature = "schemars")]
+    fn json_schema(require_quotes: bool) -> schemars::Schema {
+        crate::schema::quoted_int(true, require_quotes)
+    }
+}
 
-use alloy_primitives::U256;
-use serde::{Deserializer, Serializer};
-use serde_derive::{Deserialize, Serialize};
-use std::convert::TryFrom;
-use std::marker::PhantomData;
+/// The map key used by `serde_json`'s `arbitrary_precision` feature to pass numbers to visitors.
+const ARBITRARY_PRECISION_TOKEN: &str = "$serde_json::private::Number";
+
+/// Extract the digits of an unquoted number from the map that `serde_json` produces when its
+/// `arbitrary_precision` feature is enabled.
+///
+/// Any other map is rejected.
+fn visit_arbitrary_precision<'de, A>(mut map: A, expected: &str) -> Result<String, A::Error>
+where
+    A: MapAccess<'de>,
+{
+    match map.next_key::<String>()? {
+        Some(key) if key == ARBITRARY_PRECISION_TOKEN => map.next_value(),
+        _ => Err(serde::de::Error::invalid_type(Unexpected::Map, &expected)),
+    }
+}
 
 macro_rules! define_mod {
     ($int: ty) => {
@@ -20,13 +64,40 @@ macro_rules! define_mod {
             _phantom: PhantomData<T>,
         }
 
+        impl<T> QuotedIntVisitor<T> {
+            fn check_unquoted_allowed<E>(&self) -> Result<(), E>
+            where
+                E: serde::de::Error,
+            {
+                if self.require_quotes {
+                    Err(serde::de::Error::custom(
+                        "received unquoted integer when quotes are required",
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+
+        fn out_of_range<E, V>(v: V) -> E
+        where
+            E: serde::de::Error,
+            V: core::fmt::Display,
+        {
+            serde::de::Error::custom(format!(
+                "integer {} out of range for {}",
+                v,
+                stringify!($int)
+            ))
+        }
+
         impl<'a, T> serde::de::Visitor<'a> for QuotedIntVisitor<T>
         where
             T: From<$int> + Into<$int> + Copy + TryFrom<u64>,
         {
             type Value = T;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 if self.require_quotes {
                     write!(formatter, "a quoted integer")
                 } else {
@@ -38,22 +109,66 @@ macro_rules! define_mod {
             where
                 E: serde::de::Error,
             {
-                s.parse::<$int>()
-                    .map(T::from)
-                    .map_err(serde::de::Error::custom)
+                s.parse::<$int>().map(T::from).map_err(|e| {
+                    serde::de::Error::custom(format!("invalid integer {}: {}", Preview(s), e))
+                })
             }
 
             fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
             where
                 E: serde::de::Error,
             {
-                if self.require_quotes {
-                    Err(serde::de::Error::custom(
-                        "received unquoted integer when quotes are required",
-                    ))
-                } else {
-                    T::try_from(v).map_err(|_| serde::de::Error::custom("invalid integer"))
-                }
+                self.visit_u128(v.into())
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                self.visit_i128(v.into())
+            }
+
+            fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                self.check_unquoted_allowed()?;
+                <$int>::try_from(v)
+                    .map(T::from)
+                    .map_err(|_| out_of_range(v))
+            }
+
+            fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                self.check_unquoted_allowed()?;
+                <$int>::try_from(v)
+                    .map(T::from)
+                    .map_err(|_| out_of_range(v))
+            }
+
+            /// Unquoted integers which don't fit in a `u64` are parsed as floats by some formats
+            /// (e.g. `serde_json`), so they can't be recovered exactly.
+            fn visit_f64<E>(self, _: f64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Err(serde::de::Error::custom(
+                    "received unquoted number which cannot be represented exactly, quote it instead",
+                ))
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'a>,
+            {
+                let digits = visit_arbitrary_precision(map, "a quoted or unquoted integer")?;
+                self.check_unquoted_allowed()?;
+                digits
+                    .parse::<$int>()
+                    .map(T::from)
+                    .map_err(|_| out_of_range(digits))
             }
         }
 
@@ -82,6 +197,34 @@ macro_rules! define_mod {
             pub value: T,
         }
 
+        #[cfg(feature = "schemars")]
+        crate::schema::impl_json_schema!(
+            format!("MaybeQuoted{}", stringify!($int).to_uppercase()),
+            [T: From<$int> + Into<$int> + Copy + TryFrom<u64>] MaybeQuoted<T>,
+            <$int as QuotedInt>::json_schema(false)
+        );
+
+        #[cfg(feature = "schemars")]
+        crate::schema::impl_json_schema!(
+            format!("Quoted{}", stringify!($int).to_uppercase()),
+            [T: From<$int> + Into<$int> + Copy + TryFrom<u64>] Quoted<T>,
+            <$int as QuotedInt>::json_schema(true)
+        );
+
+        #[cfg(feature = "utoipa")]
+        crate::openapi::impl_to_schema!(
+            format!("MaybeQuoted{}", stringify!($int).to_uppercase()),
+            [T: From<$int> + Into<$int> + Copy + TryFrom<u64>] MaybeQuoted<T>,
+            openapi_schema()
+        );
+
+        #[cfg(feature = "utoipa")]
+        crate::openapi::impl_to_schema!(
+            format!("Quoted{}", stringify!($int).to_uppercase()),
+            [T: From<$int> + Into<$int> + Copy + TryFrom<u64>] Quoted<T>,
+            openapi_schema()
+        );
+
         /// Serialize with quotes.
         pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
         where
@@ -89,21 +232,53 @@ macro_rules! define_mod {
             T: From<$int> + Into<$int> + Copy,
         {
             let v: $int = (*value).into();
-            serializer.serialize_str(&format!("{}", v))
+            if !serializer.is_human_readable() {
+                return v.serialize(serializer);
+            }
+            serializer.collect_str(&v)
         }
 
-        /// Deserialize with or without quotes.
-        pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+        /// Deserialize from a human-readable format using `QuotedIntVisitor`, or natively from a
+        /// binary format.
+        fn deserialize_with<'de, D, T>(deserializer: D, require_quotes: bool) -> Result<T, D::Error>
         where
             D: Deserializer<'de>,
             T: From<$int> + Into<$int> + Copy + TryFrom<u64>,
         {
+            if !deserializer.is_human_readable() {
+                return <$int>::deserialize(deserializer).map(T::from);
+            }
             deserializer.deserialize_any(QuotedIntVisitor {
-                require_quotes: false,
+                require_quotes,
                 _phantom: PhantomData,
             })
         }
 
+        /// Deserialize with or without quotes.
+        pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+        where
+            D: Deserializer<'de>,
+            T: From<$int> + Into<$int> + Copy + TryFrom<u64>,
+        {
+            deserialize_with(deserializer, false)
+        }
+
+        /// The JSON schema of a quoted or unquoted integer.
+        ///
+        /// Usage: `#[schemars(schema_with = "quoted_u64::schema")]`.
+        #[cfg(feature = "schemars")]
+        pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+            <$int as QuotedInt>::json_schema(false)
+        }
+
+        /// The OpenAPI schema of a quoted integer.
+        ///
+        /// Usage: `#[schema(schema_with = quoted_u64::openapi_schema)]`.
+        #[cfg(feature = "utoipa")]
+        pub fn openapi_schema() -> utoipa::openapi::schema::Schema {
+            crate::openapi::quoted_int(<$int>::MIN != 0, <$int>::BITS)
+        }
+
         /// Requires quotes when deserializing.
         ///
         /// Usage: `#[serde(with = "quoted_u64::require_quotes")]`.
@@ -116,10 +291,70 @@ macro_rules! define_mod {
                 D: Deserializer<'de>,
                 T: From<$int> + Into<$int> + Copy + TryFrom<u64>,
             {
-                deserializer.deserialize_any(QuotedIntVisitor {
-                    require_quotes: true,
-                    _phantom: PhantomData,
-                })
+                deserialize_with(deserializer, true)
+            }
+
+            #[cfg(feature = "schemars")]
+            pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+                <$int as QuotedInt>::json_schema(true)
+            }
+
+            #[cfg(feature = "utoipa")]
+            pub use super::openapi_schema;
+        }
+
+        /// Formats `Option<T>` using quotes, or as `null`.
+        ///
+        /// Quotes are optional during decoding, as for the parent module.
+        ///
+        /// Usage: `#[serde(with = "quoted_u64::option", default)]`.
+        pub mod option {
+            use super::*;
+            use serde::{Deserialize, Serialize};
+
+            pub fn serialize<S, T>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+                T: From<$int> + Into<$int> + Copy + TryFrom<u64>,
+            {
+                value.map(|value| MaybeQuoted { value }).serialize(serializer)
+            }
+
+            pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+            where
+                D: Deserializer<'de>,
+                T: From<$int> + Into<$int> + Copy + TryFrom<u64>,
+            {
+                Ok(Option::<MaybeQuoted<T>>::deserialize(deserializer)?.map(|quoted| quoted.value))
+            }
+
+            #[cfg(feature = "schemars")]
+            pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+                crate::schema::nullable(<$int as QuotedInt>::json_schema(false))
+            }
+        }
+
+        impl QuotedInt for $int {
+            fn serialize_quoted<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serialize(self, serializer)
+            }
+
+            fn deserialize_quoted<'de, D>(
+                deserializer: D,
+                require_quotes: bool,
+            ) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserialize_with(deserializer, require_quotes)
+            }
+
+            #[cfg(feature = "schemars")]
+            fn json_schema(require_quotes: bool) -> schemars::Schema {
+                crate::schema::quoted_int(<$int>::MIN != 0, require_quotes)
             }
         }
 
@@ -133,6 +368,109 @@ macro_rules! define_mod {
                 assert_eq!(x.value, 8);
                 serde_json::from_str::<Quoted<$int>>("8").unwrap_err();
             }
+
+            crate::test_utils::option_wrapper!($int);
+
+            #[test]
+            fn option() {
+                crate::test_utils::check_option(
+                    OptionWrapper { val: Some(8) },
+                    r#""8""#,
+                    &[r#""eight""#],
+                );
+
+                // Quotes are optional during decoding, as for the parent module.
+                assert_eq!(
+                    serde_json::from_str::<OptionWrapper>(r#"{"val":8}"#).unwrap(),
+                    OptionWrapper { val: Some(8) }
+                );
+            }
+
+            #[test]
+            fn bounds() {
+                for value in [<$int>::MIN, <$int>::MAX] {
+                    let json = format!("\"{}\"", value);
+                    assert_eq!(
+                        serde_json::to_string(&MaybeQuoted { value }).unwrap(),
+                        json
+                    );
+                    assert_eq!(
+                        serde_json::from_str::<MaybeQuoted<$int>>(&json).unwrap().value,
+                        value
+                    );
+                }
+
+                let too_big = format!("\"{}0\"", <$int>::MAX);
+                serde_json::from_str::<MaybeQuoted<$int>>(&too_big).unwrap_err();
+            }
+
+            #[test]
+            fn unquoted() {
+                let x = serde_json::from_str::<MaybeQuoted<$int>>("8").unwrap();
+                assert_eq!(x.value, 8);
+
+                let min = serde_json::from_str::<MaybeQuoted<$int>>(&<$int>::MIN.to_string());
+                if <$int>::MIN as i128 >= i64::MIN as i128 || cfg!(feature = "arbitrary_precision") {
+                    assert_eq!(min.unwrap().value, <$int>::MIN);
+                } else {
+                    min.unwrap_err();
+                }
+
+                let negative = serde_json::from_str::<MaybeQuoted<$int>>("-8");
+                if <$int>::MIN == 0 {
+                    negative.unwrap_err();
+                } else {
+                    assert_eq!(negative.unwrap().value as i128, -8);
+                }
+                serde_json::from_str::<Quoted<$int>>("-8").unwrap_err();
+
+                // Larger than any `u64`, so `serde_json` parses it as a float unless
+                // `arbitrary_precision` is enabled.
+                let u128_max =
+                    serde_json::from_str::<MaybeQuoted<$int>>(&u128::MAX.to_string());
+                if <$int>::MAX as u128 == u128::MAX && cfg!(feature = "arbitrary_precision") {
+                    assert_eq!(u128_max.unwrap().value as u128, u128::MAX);
+                } else {
+                    u128_max.unwrap_err();
+                }
+
+                // Formats with native 128-bit integers are range-checked.
+                let de = serde::de::value::U128Deserializer::<serde::de::value::Error>::new(
+                    <$int>::MAX as u128,
+                );
+                assert_eq!(deserialize::<_, $int>(de).unwrap(), <$int>::MAX);
+                let de = serde::de::value::U128Deserializer::<serde::de::value::Error>::new(
+                    u128::MAX,
+                );
+                if (<$int>::MAX as u128) < u128::MAX {
+                    deserialize::<_, $int>(de).unwrap_err();
+                }
+                let de = serde::de::value::I128Deserializer::<serde::de::value::Error>::new(
+                    <$int>::MIN as i128,
+                );
+                assert_eq!(deserialize::<_, $int>(de).unwrap(), <$int>::MIN);
+                let de = serde::de::value::I128Deserializer::<serde::de::value::Error>::new(
+                    i128::MIN,
+                );
+                if (<$int>::MIN as i128) > i128::MIN {
+                    deserialize::<_, $int>(de).unwrap_err();
+                }
+            }
+
+            #[test]
+            fn binary() {
+                let value = <$int>::MAX;
+                let bytes = bincode::serialize(&MaybeQuoted { value }).unwrap();
+                assert_eq!(bytes, value.to_le_bytes());
+                assert_eq!(
+                    bincode::deserialize::<Quoted<$int>>(&bytes).unwrap().value,
+                    value
+                );
+
+                let some = OptionWrapper { val: Some(8) };
+                let bytes = bincode::serialize(&some).unwrap();
+                assert_eq!(bincode::deserialize::<OptionWrapper>(&bytes).unwrap(), some);
+            }
         }
     };
 }
@@ -143,6 +481,12 @@ pub mod quoted_u8 {
     define_mod!(u8);
 }
 
+pub mod quoted_u16 {
+    use super::*;
+
+    define_mod!(u16);
+}
+
 pub mod quoted_u32 {
     use super::*;
 
@@ -155,57 +499,329 @@ pub mod quoted_u64 {
     define_mod!(u64);
 }
 
+pub mod quoted_u128 {
+    use super::*;
+
+    define_mod!(u128);
+}
+
+pub mod quoted_usize {
+    use super::*;
+
+    define_mod!(usize);
+}
+
+pub mod quoted_i32 {
+    use super::*;
+
+    define_mod!(i32);
+}
+
 pub mod quoted_i64 {
     use super::*;
 
     define_mod!(i64);
 }
 
+pub mod quoted_i128 {
+    use super::*;
+
+    define_mod!(i128);
+}
+
+/// Formats a `U256` using quotes.
+///
+/// Works for the `U256` of any backend implementing `Uint256`.
 pub mod quoted_u256 {
     use super::*;
+    use crate::Uint256;
+
+    /// Serde support for deserializing a `U256` as a decimal integer.
+    ///
+    /// Configurable so that quotes are either required or optional.
+    pub struct U256Visitor<T> {
+        require_quotes: bool,
+        _phantom: PhantomData<T>,
+    }
+
+    impl<T> U256Visitor<T> {
+        fn new(require_quotes: bool) -> Self {
+            Self {
+                require_quotes,
+                _phantom: PhantomData,
+            }
+        }
 
-    struct U256Visitor;
+        fn check_unquoted_allowed<E>(&self) -> Result<(), E>
+        where
+            E: serde::de::Error,
+        {
+            if self.require_quotes {
+                Err(serde::de::Error::custom(
+                    "received unquoted integer when quotes are required",
+                ))
+            } else {
+                Ok(())
+            }
+        }
+    }
 
-    impl<'de> serde::de::Visitor<'de> for U256Visitor {
-        type Value = U256;
+    impl<'de, T: Uint256> serde::de::Visitor<'de> for U256Visitor<T> {
+        type Value = T;
 
-        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-            formatter.write_str("a quoted U256 integer")
+        fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+            if self.require_quotes {
+                formatter.write_str("a quoted U256 integer")
+            } else {
+                formatter.write_str("a quoted or unquoted U256 integer")
+            }
         }
 
         fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
         where
             E: serde::de::Error,
         {
-            U256::from_str_radix(v, 10).map_err(serde::de::Error::custom)
+            crate::uint256::parse_dec::<T>(v).map_err(|e| {
+                serde::de::Error::custom(format!("invalid integer {}: {}", Preview(v), e))
+            })
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            self.visit_u128(v.into())
+        }
+
+        fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            self.check_unquoted_allowed()?;
+            Ok(T::from_u128(v))
         }
+
+        /// Unquoted integers which don't fit in a `u64` are parsed as floats by `serde_json`
+        /// unless its `arbitrary_precision` feature is enabled.
+        fn visit_f64<E>(self, _: f64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Err(serde::de::Error::custom(
+                "received unquoted number which cannot be represented exactly, quote it or enable \
+                 the arbitrary_precision feature",
+            ))
+        }
+
+        fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let digits = visit_arbitrary_precision(map, "a quoted or unquoted U256 integer")?;
+            self.check_unquoted_allowed()?;
+            self.visit_str(&digits)
+        }
+    }
+
+    /// Compositional wrapper type that allows quotes or no quotes.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+    #[serde(transparent)]
+    pub struct MaybeQuoted<T>
+    where
+        T: Uint256,
+    {
+        #[serde(with = "maybe_quoted")]
+        pub value: T,
+    }
+
+    /// Wrapper type for requiring quotes on the `U256` of any backend.
+    ///
+    /// Unlike using `serde(with = "quoted_u256")` this is composable, and can be nested inside
+    /// types like `Option`, `Result` and `Vec`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+    #[serde(transparent)]
+    pub struct Quoted<T>
+    where
+        T: Uint256,
+    {
+        #[serde(with = "self")]
+        pub value: T,
     }
 
+    #[cfg(feature = "schemars")]
+    crate::schema::impl_json_schema!(
+        "MaybeQuotedU256",
+        [T: Uint256] MaybeQuoted<T>,
+        T::json_schema(false)
+    );
+
+    #[cfg(feature = "schemars")]
+    crate::schema::impl_json_schema!("QuotedU256", [T: Uint256] Quoted<T>, T::json_schema(true));
+
+    #[cfg(feature = "utoipa")]
+    crate::openapi::impl_to_schema!(
+        "MaybeQuotedU256",
+        [T: Uint256] MaybeQuoted<T>,
+        openapi_schema()
+    );
+
+    #[cfg(feature = "utoipa")]
+    crate::openapi::impl_to_schema!("QuotedU256", [T: Uint256] Quoted<T>, openapi_schema());
+
     /// Serialize with quotes.
-    pub fn serialize<S>(value: &U256, serializer: S) -> Result<S::Ok, S::Error>
+    pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
+        T: Uint256,
     {
-        serializer.serialize_str(&format!("{}", value))
+        if !serializer.is_human_readable() {
+            return value.serialize_binary(serializer);
+        }
+        serializer.collect_str(value)
     }
 
     /// Deserialize with quotes.
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<U256, D::Error>
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
     where
         D: Deserializer<'de>,
+        T: Uint256,
     {
-        deserializer.deserialize_str(U256Visitor)
+        if !deserializer.is_human_readable() {
+            return T::deserialize_binary(deserializer);
+        }
+        deserializer.deserialize_str(U256Visitor::new(true))
+    }
+
+    /// The JSON schema of a quoted `U256`.
+    ///
+    /// Usage: `#[schemars(schema_with = "quoted_u256::schema")]`.
+    #[cfg(feature = "schemars")]
+    pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::schema::quoted_int(false, true)
+    }
+
+    /// The OpenAPI schema of a quoted `U256`.
+    ///
+    /// Usage: `#[schema(schema_with = quoted_u256::openapi_schema)]`.
+    #[cfg(feature = "utoipa")]
+    pub fn openapi_schema() -> utoipa::openapi::schema::Schema {
+        crate::openapi::quoted_int(false, 256)
+    }
+
+    impl<T: Uint256> QuotedInt for T {
+        fn serialize_quoted<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serialize(self, serializer)
+        }
+
+        fn deserialize_quoted<'de, D>(
+            deserializer: D,
+            require_quotes: bool,
+        ) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            if require_quotes {
+                deserialize(deserializer)
+            } else {
+                maybe_quoted::deserialize(deserializer)
+            }
+        }
+
+        #[cfg(feature = "schemars")]
+        fn json_schema(require_quotes: bool) -> schemars::Schema {
+            crate::schema::quoted_int(false, require_quotes)
+        }
+    }
+
+    /// Formats `Option<U256>` using quotes, or as `null`.
+    ///
+    /// Quotes are required during decoding, as for the parent module.
+    ///
+    /// Usage: `#[serde(with = "quoted_u256::option", default)]`.
+    pub mod option {
+        use super::*;
+
+        #[derive(Serialize, Deserialize)]
+        #[serde(transparent, bound = "T: Uint256")]
+        struct Wrapper<T>(#[serde(with = "super")] T);
+
+        pub fn serialize<S, T>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+            T: Uint256,
+        {
+            value.map(Wrapper).serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+        where
+            D: Deserializer<'de>,
+            T: Uint256,
+        {
+            Ok(Option::<Wrapper<T>>::deserialize(deserializer)?.map(|wrapper| wrapper.0))
+        }
+
+        #[cfg(feature = "schemars")]
+        pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+            crate::schema::nullable(crate::schema::quoted_int(false, true))
+        }
+    }
+
+    /// Requires quotes when deserializing, which is the default for `quoted_u256`.
+    ///
+    /// Usage: `#[serde(with = "quoted_u256::require_quotes")]`.
+    pub mod require_quotes {
+        #[cfg(feature = "schemars")]
+        pub use super::schema;
+
+        #[cfg(feature = "utoipa")]
+        pub use super::openapi_schema;
+        pub use super::{deserialize, serialize};
+    }
+
+    /// Allows quotes to be omitted when deserializing.
+    ///
+    /// Unquoted integers larger than a `u64` are only decoded exactly when `serde_json`'s
+    /// `arbitrary_precision` feature is enabled (e.g. via this crate's feature of the same name).
+    ///
+    /// Usage: `#[serde(with = "quoted_u256::maybe_quoted")]`.
+    pub mod maybe_quoted {
+        pub use super::serialize;
+        use super::*;
+
+        pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+        where
+            D: Deserializer<'de>,
+            T: Uint256,
+        {
+            if !deserializer.is_human_readable() {
+                return T::deserialize_binary(deserializer);
+            }
+            deserializer.deserialize_any(U256Visitor::new(false))
+        }
+
+        #[cfg(feature = "schemars")]
+        pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+            crate::schema::quoted_int(false, false)
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    #[cfg(any(feature = "alloy", feature = "ruint"))]
+    use crate::ruint::aliases::U256;
 
+    #[cfg(any(feature = "alloy", feature = "ruint"))]
     #[derive(Debug, PartialEq, Serialize, Deserialize)]
     #[serde(transparent)]
     struct WrappedU256(#[serde(with = "quoted_u256")] U256);
 
+    #[cfg(any(feature = "alloy", feature = "ruint"))]
     #[test]
     fn u256_with_quotes() {
         assert_eq!(
@@ -218,9 +834,135 @@ mod test {
         );
     }
 
+    #[cfg(any(feature = "alloy", feature = "ruint"))]
     #[test]
     fn u256_without_quotes() {
         serde_json::from_str::<WrappedU256>("1").unwrap_err();
+        serde_json::from_str::<quoted_u256::Quoted<U256>>("1").unwrap_err();
+    }
+
+    #[cfg(any(feature = "alloy", feature = "ruint"))]
+    #[test]
+    fn u256_maybe_quoted() {
+        for (json, expected) in [
+            ("\"1\"", U256::from(1)),
+            ("1", U256::from(1)),
+            ("18446744073709551615", U256::from(u64::MAX)),
+        ] {
+            assert_eq!(
+                serde_json::from_str::<quoted_u256::MaybeQuoted<U256>>(json)
+                    .unwrap()
+                    .value,
+                expected
+            );
+        }
+        assert_eq!(
+            serde_json::to_string(&quoted_u256::MaybeQuoted {
+                value: U256::from(1)
+            })
+            .unwrap(),
+            "\"1\""
+        );
+        serde_json::from_str::<quoted_u256::MaybeQuoted<U256>>("-1").unwrap_err();
+        serde_json::from_str::<quoted_u256::MaybeQuoted<U256>>("1.5").unwrap_err();
+        serde_json::from_str::<quoted_u256::MaybeQuoted<U256>>("{}").unwrap_err();
+    }
+
+    #[cfg(all(
+        any(feature = "alloy", feature = "ruint"),
+        not(feature = "arbitrary_precision")
+    ))]
+    #[test]
+    fn u256_unquoted_lossy() {
+        serde_json::from_str::<quoted_u256::MaybeQuoted<U256>>("18446744073709551616").unwrap_err();
+    }
+
+    #[cfg(all(
+        any(feature = "alloy", feature = "ruint"),
+        feature = "arbitrary_precision"
+    ))]
+    #[test]
+    fn u256_unquoted_arbitrary_precision() {
+        let max = U256::MAX.to_string();
+        assert_eq!(
+            serde_json::from_str::<quoted_u256::MaybeQuoted<U256>>(&max)
+                .unwrap()
+                .value,
+            U256::MAX
+        );
+        // One more than `U256::MAX`.
+        serde_json::from_str::<quoted_u256::MaybeQuoted<U256>>(
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936",
+        )
+        .unwrap_err();
+        serde_json::from_str::<quoted_u256::MaybeQuoted<U256>>("1.5").unwrap_err();
+        serde_json::from_str::<quoted_u256::MaybeQuoted<U256>>("1e3").unwrap_err();
+        serde_json::from_str::<quoted_u256::Quoted<U256>>(&max).unwrap_err();
+    }
+
+    #[cfg(feature = "arbitrary_precision")]
+    #[test]
+    fn unquoted_arbitrary_precision() {
+        assert_eq!(
+            serde_json::from_str::<quoted_u128::MaybeQuoted<u128>>(&u128::MAX.to_string())
+                .unwrap()
+                .value,
+            u128::MAX
+        );
+        assert_eq!(
+            serde_json::from_str::<quoted_i128::MaybeQuoted<i128>>(&i128::MIN.to_string())
+                .unwrap()
+                .value,
+            i128::MIN
+        );
+        serde_json::from_str::<quoted_u64::MaybeQuoted<u64>>("18446744073709551616").unwrap_err();
+        serde_json::from_str::<quoted_u64::Quoted<u64>>("1").unwrap_err();
+    }
+
+    #[cfg(any(feature = "alloy", feature = "ruint"))]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct OptionalU256 {
+        #[serde(with = "quoted_u256::option", default)]
+        val: Option<U256>,
+    }
+
+    #[cfg(any(feature = "alloy", feature = "ruint"))]
+    #[test]
+    fn u256_option() {
+        let some = OptionalU256 {
+            val: Some(U256::from(1)),
+        };
+        assert_eq!(serde_json::to_string(&some).unwrap(), r#"{"val":"1"}"#);
+        assert_eq!(
+            serde_json::from_str::<OptionalU256>(r#"{"val":"1"}"#).unwrap(),
+            some
+        );
+        let none = OptionalU256 { val: None };
+        assert_eq!(serde_json::to_string(&none).unwrap(), r#"{"val":null}"#);
+        for json in [r#"{"val":null}"#, "{}"] {
+            assert_eq!(serde_json::from_str::<OptionalU256>(json).unwrap(), none);
+        }
+        serde_json::from_str::<OptionalU256>(r#"{"val":1}"#).unwrap_err();
+    }
+
+    #[cfg(any(feature = "alloy", feature = "ruint"))]
+    #[test]
+    fn u256_binary() {
+        let value = U256::from(0x0102);
+        let bytes = bincode::serialize(&WrappedU256(value)).unwrap();
+        let mut expected = vec![32, 0, 0, 0, 0, 0, 0, 0];
+        expected.extend_from_slice(&value.to_be_bytes::<32>());
+        assert_eq!(bytes, expected);
+        assert_eq!(
+            bincode::deserialize::<WrappedU256>(&bytes).unwrap(),
+            WrappedU256(value)
+        );
+        assert_eq!(
+            bincode::deserialize::<quoted_u256::MaybeQuoted<U256>>(&bytes)
+                .unwrap()
+                .value,
+            value
+        );
     }
 
     #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -239,9 +981,14 @@ mod test {
         );
     }
 
-    // It would be OK if this worked, but we don't need it to (i64s should always be quoted).
     #[test]
     fn negative_i64_without_quotes() {
-        serde_json::from_str::<WrappedI64>("-200").unwrap_err();
+        assert_eq!(serde_json::from_str::<WrappedI64>("-200").unwrap().0, -200);
+        assert_eq!(
+            serde_json::from_str::<WrappedI64>(&i64::MIN.to_string())
+                .unwrap()
+                .0,
+            i64::MIN
+        );
     }
 }
@@ -0,0 +1,310 @@
+//! Formats a collection of integers as a list of quoted integers.
+//!
+//! E.g., `vec![0, 1, 2]` serializes as `["0", "1", "2"]`.
+//!
+//! Works for any element type implementing `QuotedInt` (i.e. the integer type of any `quoted_*`
+//! module), and any collection that can be iterated by reference and built with `FromIterator`,
+//! such as `Vec`, `VecDeque`, `BTreeSet` and `HashSet`. Fixed-length arrays are supported by the
+//! `array` submodule.
+//!
+//! Quotes can be optional during decoding.
+
+use crate::{seq, QuotedInt};
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{DeserializeSeed, SeqAccess, Visitor};
+use serde::ser::Serialize;
+use serde::{Deserializer, Serializer};
+
+/// Serializes a single element with quotes.
+struct QuotedElement<'a, T>(&'a T);
+
+impl<T: QuotedInt> Serialize for QuotedElement<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize_quoted(serializer)
+    }
+}
+
+/// Deserializes a single element, with or without quotes.
+struct QuotedElementSeed<T> {
+    require_quotes: bool,
+    _phantom: PhantomData<T>,
+}
+
+impl<'de, T: QuotedInt> DeserializeSeed<'de> for QuotedElementSeed<T> {
+    type Value = T;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize_quoted(deserializer, self.require_quotes)
+    }
+}
+
+/// Serde support for deserializing a list of quoted integers into any `FromIterator` collection.
+///
+/// Configurable so that quotes are either required or optional.
+pub struct QuotedSeqVisitor<C, T> {
+    require_quotes: bool,
+    _phantom: PhantomData<(C, T)>,
+}
+
+impl<C, T> QuotedSeqVisitor<C, T> {
+    pub fn new(require_quotes: bool) -> Self {
+        Self {
+            require_quotes,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'de, C, T> Visitor<'de> for QuotedSeqVisitor<C, T>
+where
+    C: FromIterator<T>,
+    T: QuotedInt,
+{
+    type Value = C;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.require_quotes {
+            write!(formatter, "a list of quoted integers")
+        } else {
+            write!(formatter, "a list of quoted or unquoted integers")
+        }
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        seq::collect(seq, || QuotedElementSeed {
+            require_quotes: self.require_quotes,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+/// Serialize with quotes.
+pub fn serialize<S, C, T>(value: &C, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    C: ?Sized,
+    for<'a> &'a C: IntoIterator<Item = &'a T>,
+    T: QuotedInt,
+{
+    serializer.collect_seq(value.into_iter().map(QuotedElement))
+}
+
+/// Deserialize with or without quotes.
+pub fn deserialize<'de, D, C, T>(deserializer: D) -> Result<C, D::Error>
+where
+    D: Deserializer<'de>,
+    C: FromIterator<T>,
+    T: QuotedInt,
+{
+    deserializer.deserialize_seq(QuotedSeqVisitor::new(false))
+}
+
+/// The JSON schema of a list of quoted or unquoted `T`s.
+///
+/// Usage: `#[schemars(schema_with = "quoted_seq::schema::<u64>")]`.
+#[cfg(feature = "schemars")]
+pub fn schema<T: QuotedInt>(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    crate::schema::array(T::json_schema(false), None)
+}
+
+/// Requires quotes when deserializing.
+///
+/// Usage: `#[serde(with = "quoted_seq::require_quotes")]`.
+pub mod require_quotes {
+    pub use super::serialize;
+    use super::*;
+
+    pub fn deserialize<'de, D, C, T>(deserializer: D) -> Result<C, D::Error>
+    where
+        D: Deserializer<'de>,
+        C: FromIterator<T>,
+        T: QuotedInt,
+    {
+        deserializer.deserialize_seq(QuotedSeqVisitor::new(true))
+    }
+
+    #[cfg(feature = "schemars")]
+    pub fn schema<T: QuotedInt>(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::schema::array(T::json_schema(true), None)
+    }
+}
+
+/// Formats `[T; N]` as a list of quoted integers.
+///
+/// Lists of any other length are rejected during decoding.
+pub mod array {
+    pub use super::serialize;
+    use super::*;
+
+    /// Deserialize with or without quotes.
+    pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+    where
+        D: Deserializer<'de>,
+        T: QuotedInt,
+    {
+        seq::to_array(super::deserialize(deserializer)?)
+    }
+
+    /// The JSON schema of a list of exactly `N` quoted or unquoted `T`s.
+    ///
+    /// Usage: `#[schemars(schema_with = "quoted_seq::array::schema::<u64, 4>")]`.
+    #[cfg(feature = "schemars")]
+    pub fn schema<T: QuotedInt, const N: usize>(
+        _: &mut schemars::SchemaGenerator,
+    ) -> schemars::Schema {
+        crate::schema::array(T::json_schema(false), Some(N))
+    }
+
+    /// Requires quotes when deserializing.
+    ///
+    /// Usage: `#[serde(with = "quoted_seq::array::require_quotes")]`.
+    pub mod require_quotes {
+        pub use super::serialize;
+        use super::*;
+
+        pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+        where
+            D: Deserializer<'de>,
+            T: QuotedInt,
+        {
+            seq::to_array(super::super::require_quotes::deserialize(deserializer)?)
+        }
+
+        #[cfg(feature = "schemars")]
+        pub fn schema<T: QuotedInt, const N: usize>(
+            _: &mut schemars::SchemaGenerator,
+        ) -> schemars::Schema {
+            crate::schema::array(T::json_schema(true), Some(N))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::{Deserialize, Serialize};
+    use std::collections::{BTreeSet, HashSet, VecDeque};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Obj {
+        #[serde(with = "super")]
+        bytes: Vec<u8>,
+        #[serde(with = "super")]
+        words: VecDeque<u32>,
+        #[serde(with = "super")]
+        deltas: Vec<i64>,
+        #[serde(with = "super")]
+        balances: Vec<u128>,
+        #[serde(with = "super")]
+        indices: BTreeSet<u64>,
+        #[serde(with = "super::array")]
+        fixed: [u64; 2],
+    }
+
+    #[test]
+    fn round_trip() {
+        let obj = Obj {
+            bytes: vec![0, 255],
+            words: VecDeque::from(vec![1, u32::MAX]),
+            deltas: vec![-5, 5],
+            balances: vec![u128::MAX],
+            indices: BTreeSet::from([3, 1, 2]),
+            fixed: [7, 8],
+        };
+        let json = serde_json::to_string(&obj).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"bytes":["0","255"],"words":["1","4294967295"],"deltas":["-5","5"],"balances":["{}"],"indices":["1","2","3"],"fixed":["7","8"]}}"#,
+                u128::MAX
+            )
+        );
+        assert_eq!(serde_json::from_str::<Obj>(&json).unwrap(), obj);
+    }
+
+    #[test]
+    fn unquoted_and_mixed() {
+        let obj: Obj = serde_json::from_str(
+            r#"{"bytes":[0,"1"],"words":[1],"deltas":[-5,"-6"],"balances":[1,"2"],"indices":[2,2,1],"fixed":["7",8]}"#,
+        )
+        .unwrap();
+        assert_eq!(obj.bytes, vec![0, 1]);
+        assert_eq!(obj.deltas, vec![-5, -6]);
+        assert_eq!(obj.balances, vec![1, 2]);
+        assert_eq!(obj.indices, BTreeSet::from([1, 2]));
+        assert_eq!(obj.fixed, [7, 8]);
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct ValidatorSet {
+        #[serde(with = "super::require_quotes")]
+        indices: HashSet<u64>,
+    }
+
+    #[test]
+    fn require_quotes() {
+        let set: ValidatorSet = serde_json::from_str(r#"["1", "2", "1"]"#).unwrap();
+        assert_eq!(set.indices, HashSet::from([1, 2]));
+        serde_json::from_str::<ValidatorSet>(r#"["1", 2]"#).unwrap_err();
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct Fixed {
+        #[serde(with = "super::array::require_quotes")]
+        values: [u64; 3],
+    }
+
+    #[test]
+    fn array() {
+        assert_eq!(
+            serde_json::from_str::<Fixed>(r#"["1", "2", "3"]"#)
+                .unwrap()
+                .values,
+            [1, 2, 3]
+        );
+        serde_json::from_str::<Fixed>(r#"["1", "2"]"#).unwrap_err();
+        serde_json::from_str::<Fixed>(r#"["1", "2", "3", "4"]"#).unwrap_err();
+        serde_json::from_str::<Fixed>(r#"["1", "2", 3]"#).unwrap_err();
+    }
+
+    #[test]
+    fn errors() {
+        // Out of range for the element type.
+        serde_json::from_str::<Obj>(
+            r#"{"bytes":["256"],"words":[],"deltas":[],"balances":[],"indices":[],"fixed":[1,2]}"#,
+        )
+        .unwrap_err();
+        // Not a list.
+        serde_json::from_str::<Fixed>(r#""[1, 2, 3]""#).unwrap_err();
+    }
+
+    #[test]
+    fn binary() {
+        let obj = Obj {
+            bytes: vec![0, 255],
+            words: VecDeque::from(vec![1]),
+            deltas: vec![-5],
+            balances: vec![u128::MAX],
+            indices: BTreeSet::from([3]),
+            fixed: [7, 8],
+        };
+        let bytes = bincode::serialize(&obj).unwrap();
+        assert_eq!(bincode::deserialize::<Obj>(&bytes).unwrap(), obj);
+
+        let fixed = Fixed { values: [1, 2, 3] };
+        let bytes = bincode::serialize(&fixed).unwrap();
+        assert_eq!(bytes[8..16], 1u64.to_le_bytes());
+        assert_eq!(bincode::deserialize::<Fixed>(&bytes).unwrap(), fixed);
+    }
+}
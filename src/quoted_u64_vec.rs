@@ -3,8 +3,11 @@
 //! E.g., `vec![0, 1, 2]` serializes as `["0", "1", "2"]`.
 //!
 //! Quotes can be optional during decoding.
+//!
+//! This is `quoted_seq` specialised to `Vec<u64>`.
 
-use serde::ser::SerializeSeq;
+use crate::quoted_seq::{self, QuotedSeqVisitor};
+use alloc::vec::Vec;
 use serde::{Deserializer, Serializer};
 use serde_derive::{Deserialize, Serialize};
 
@@ -15,26 +18,26 @@ pub struct QuotedIntWrapper {
     pub int: u64,
 }
 
+#[cfg(feature = "schemars")]
+crate::schema::impl_json_schema!(
+    "QuotedIntWrapper",
+    [] QuotedIntWrapper,
+    <u64 as crate::QuotedInt>::json_schema(false)
+);
+
 pub struct QuotedIntVecVisitor;
 impl<'a> serde::de::Visitor<'a> for QuotedIntVecVisitor {
     type Value = Vec<u64>;
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(formatter, "a list of quoted or unquoted integers")
     }
 
-    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
     where
         A: serde::de::SeqAccess<'a>,
     {
-        let mut vec = vec![];
-
-        while let Some(val) = seq.next_element()? {
-            let val: QuotedIntWrapper = val;
-            vec.push(val.int);
-        }
-
-        Ok(vec)
+        QuotedSeqVisitor::new(false).visit_seq(seq)
     }
 }
 
@@ -42,25 +45,29 @@ pub fn serialize<S>(value: &[u64], serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    let mut seq = serializer.serialize_seq(Some(value.len()))?;
-    for &int in value {
-        seq.serialize_element(&QuotedIntWrapper { int })?;
-    }
-    seq.end()
+    quoted_seq::serialize(value, serializer)
 }
 
 pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u64>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    deserializer.deserialize_any(QuotedIntVecVisitor)
+    deserializer.deserialize_seq(QuotedIntVecVisitor)
+}
+
+/// The JSON schema of a list of quoted or unquoted `u64`s.
+///
+/// Usage: `#[schemars(schema_with = "quoted_u64_vec::schema")]`.
+#[cfg(feature = "schemars")]
+pub fn schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    quoted_seq::schema::<u64>(generator)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    #[derive(Debug, Serialize, Deserialize)]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
     struct Obj {
         #[serde(with = "crate::quoted_u64_vec")]
         values: Vec<u64>,
@@ -94,4 +101,27 @@ mod test {
     fn whole_list_quoted_err() {
         serde_json::from_str::<Obj>(r#"{ "values": "[1, 2, 3, 4]" }"#).unwrap_err();
     }
+
+    #[test]
+    fn binary() {
+        let obj = Obj {
+            values: vec![1, u64::MAX],
+        };
+        let bytes = bincode::serialize(&obj).unwrap();
+        assert_eq!(bytes.len(), 8 + 2 * 8);
+        assert_eq!(bincode::deserialize::<Obj>(&bytes).unwrap(), obj);
+    }
+
+    #[test]
+    fn error_position() {
+        let err = serde_json::from_str::<Obj>(r#"{ "values": ["1", "2", "three"] }"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with(r#"element 2: invalid integer "three": invalid digit found in string"#));
+
+        let err = serde_json::from_str::<Obj>(r#"{ "values": [1, -2] }"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("element 1: integer -2 out of range for u64"));
+    }
 }
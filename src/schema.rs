@@ -0,0 +1,244 @@
+//! JSON schemas describing the wire format of each module, for use with `schemars`.
+//!
+//! Every module provides a `schema` function for `#[schemars(schema_with = "...")]`, and the
+//! composable wrapper types implement `JsonSchema` for `#[schemars(with = "...")]`.
+
+use crate::patterns::{hex_len, HEX_PATTERN, INT_PATTERN, UINT_PATTERN};
+use schemars::{json_schema, Schema};
+
+/// A 0x-prefixed hex string of any number of bytes.
+pub(crate) fn hex_bytes() -> Schema {
+    json_schema!({
+        "type": "string",
+        "pattern": HEX_PATTERN,
+    })
+}
+
+/// A 0x-prefixed hex string of at most `max` bytes.
+pub(crate) fn bounded_hex_bytes(max: usize) -> Schema {
+    json_schema!({
+        "type": "string",
+        "pattern": HEX_PATTERN,
+        "maxLength": hex_len(max),
+    })
+}
+
+/// A 0x-prefixed hex string of exactly `len` bytes.
+pub(crate) fn fixed_hex_bytes(len: usize) -> Schema {
+    json_schema!({
+        "type": "string",
+        "pattern": HEX_PATTERN,
+        "minLength": hex_len(len),
+        "maxLength": hex_len(len),
+    })
+}
+
+/// A 0x-prefixed address with an EIP-55 mixed-case checksum.
+#[cfg(feature = "alloy")]
+pub(crate) fn checksummed_address() -> Schema {
+    json_schema!({
+        "type": "string",
+        "pattern": crate::patterns::CHECKSUMMED_ADDRESS_PATTERN,
+    })
+}
+
+/// A 0x-prefixed hex quantity of at most `bits` bits, with no leading zeros.
+pub(crate) fn quantity(bits: usize) -> Schema {
+    json_schema!({
+        "type": "string",
+        "pattern": "^0x(0|[1-9a-f][0-9a-f]*)$",
+        "maxLength": 2 + bits.div_ceil(4),
+    })
+}
+
+/// A quoted decimal integer, or an unquoted one too unless `require_quotes` is set.
+pub(crate) fn quoted_int(signed: bool, require_quotes: bool) -> Schema {
+    let pattern = if signed { INT_PATTERN } else { UINT_PATTERN };
+    let quoted = json_schema!({
+        "type": "string",
+        "pattern": pattern,
+    });
+
+    if require_quotes {
+        quoted
+    } else if signed {
+        json_schema!({ "anyOf": [quoted, { "type": "integer" }] })
+    } else {
+        json_schema!({ "anyOf": [quoted, { "type": "integer", "minimum": 0 }] })
+    }
+}
+
+/// A list of `items`, of exactly `len` items if provided.
+pub(crate) fn array(items: Schema, len: Option<usize>) -> Schema {
+    let mut schema = json_schema!({
+        "type": "array",
+        "items": items,
+    });
+    if let Some(len) = len {
+        schema.insert("minItems".into(), len.into());
+        schema.insert("maxItems".into(), len.into());
+    }
+    schema
+}
+
+/// A list of at most `max` `items`.
+pub(crate) fn bounded_array(items: Schema, max: usize) -> Schema {
+    let mut schema = array(items, None);
+    schema.insert("maxItems".into(), max.into());
+    schema
+}
+
+/// `schema`, or `null`.
+pub(crate) fn nullable(schema: Schema) -> Schema {
+    json_schema!({ "anyOf": [schema, { "type": "null" }] })
+}
+
+/// Implement `JsonSchema` for a wrapper type, inlining `$schema` wherever the type is used.
+///
+/// Usage: `impl_json_schema!(format!("HexArray{}", N), [const N: usize] HexArray<N>,
+/// fixed_hex_bytes(N))`.
+macro_rules! impl_json_schema {
+    ($name: expr, [$($generics: tt)*] $ty: ty, $schema: expr) => {
+        impl<$($generics)*> schemars::JsonSchema for $ty {
+            fn inline_schema() -> bool {
+                true
+            }
+
+            fn schema_name() -> alloc::borrow::Cow<'static, str> {
+                $name.into()
+            }
+
+            fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+                $schema
+            }
+        }
+    };
+}
+
+pub(crate) use impl_json_schema;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use schemars::JsonSchema;
+    use serde_json::json;
+
+    #[test]
+    fn patterns() {
+        assert_eq!(
+            fixed_hex_bytes(32).as_value(),
+            &json!({
+                "type": "string",
+                "pattern": "^0x[0-9a-f]*$",
+                "minLength": 66,
+                "maxLength": 66,
+            })
+        );
+        assert_eq!(quantity(64).get("maxLength"), Some(&json!(18)));
+        assert_eq!(quantity(7).get("maxLength"), Some(&json!(4)));
+        assert_eq!(
+            quoted_int(false, false).as_value(),
+            &json!({
+                "anyOf": [
+                    { "type": "string", "pattern": "^(0|[1-9][0-9]*)$" },
+                    { "type": "integer", "minimum": 0 },
+                ]
+            })
+        );
+        assert_eq!(
+            array(hex_bytes(), Some(2)).as_value(),
+            &json!({
+                "type": "array",
+                "items": { "type": "string", "pattern": "^0x[0-9a-f]*$" },
+                "minItems": 2,
+                "maxItems": 2,
+            })
+        );
+    }
+
+    #[test]
+    fn modules() {
+        let mut generator = schemars::SchemaGenerator::default();
+
+        assert_eq!(
+            crate::u64_hex_be::schema(&mut generator),
+            crate::quantity::schema::<u64>(&mut generator)
+        );
+        assert_eq!(
+            crate::quoted_u64::require_quotes::schema(&mut generator).as_value(),
+            &json!({ "type": "string", "pattern": "^(0|[1-9][0-9]*)$" })
+        );
+        assert_eq!(
+            crate::quoted_i64::schema(&mut generator),
+            quoted_int(true, false)
+        );
+        assert_eq!(
+            crate::quoted_seq::array::schema::<u8, 3>(&mut generator),
+            array(quoted_int(false, false), Some(3))
+        );
+        assert_eq!(
+            crate::hex_seq::schema::<[u8; 32]>(&mut generator),
+            array(fixed_hex_bytes(32), None)
+        );
+    }
+
+    /// Wrapper types are inlined wherever they are used.
+    #[test]
+    fn wrappers() {
+        let mut generator = schemars::SchemaGenerator::default();
+
+        assert_eq!(
+            generator.subschema_for::<Vec<crate::HexArray<32>>>(),
+            array(fixed_hex_bytes(32), None)
+        );
+        assert_eq!(
+            generator.subschema_for::<crate::quoted_u64::Quoted<u64>>(),
+            quoted_int(false, true)
+        );
+        assert_eq!(
+            generator.subschema_for::<crate::BoundedHexBytes<4>>(),
+            bounded_hex_bytes(4)
+        );
+        assert!(generator.definitions().is_empty());
+    }
+
+    /// Each width and length of a generic wrapper type has its own name, as for `utoipa`.
+    #[test]
+    fn names() {
+        assert_eq!(crate::quoted_u64::Quoted::<u64>::schema_name(), "QuotedU64");
+        assert_eq!(
+            crate::quoted_i32::MaybeQuoted::<i32>::schema_name(),
+            "MaybeQuotedI32"
+        );
+        assert_eq!(crate::HexArray::<4>::schema_name(), "HexArray4");
+        assert_eq!(
+            crate::BoundedHexBytes::<32>::schema_name(),
+            "BoundedHexBytes32"
+        );
+    }
+
+    #[cfg(feature = "alloy")]
+    #[test]
+    fn alloy() {
+        let mut generator = schemars::SchemaGenerator::default();
+
+        assert_eq!(crate::b256_hex::schema(&mut generator), fixed_hex_bytes(32));
+        assert_eq!(
+            crate::address_hex::schema(&mut generator),
+            fixed_hex_bytes(20)
+        );
+        assert_eq!(
+            crate::hex_seq::schema::<alloy_primitives::B256>(&mut generator),
+            array(fixed_hex_bytes(32), None)
+        );
+        assert_eq!(
+            generator.subschema_for::<Vec<crate::HexB256>>(),
+            array(fixed_hex_bytes(32), None)
+        );
+        assert_eq!(
+            crate::quoted_u256::Quoted::<alloy_primitives::U256>::schema_name(),
+            "QuotedU256"
+        );
+        assert!(generator.definitions().is_empty());
+    }
+}
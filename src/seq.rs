@@ -0,0 +1,44 @@
+//! Deserialization helpers shared by the `quoted_seq` and `hex_seq` modules.
+
+use alloc::{format, vec::Vec};
+use serde::de::{DeserializeSeed, Error, SeqAccess};
+
+/// Collect the elements of `seq` into any `FromIterator` collection, deserializing each with a
+/// seed from `seed`.
+///
+/// Elements are collected as they are decoded, without an intermediate `Vec`. Errors are prefixed
+/// with the index of the element.
+pub(crate) fn collect<'de, A, S, C>(mut seq: A, mut seed: impl FnMut() -> S) -> Result<C, A::Error>
+where
+    A: SeqAccess<'de>,
+    S: DeserializeSeed<'de>,
+    C: FromIterator<S::Value>,
+{
+    let mut error = None;
+    let mut index = 0;
+    let collection = core::iter::from_fn(|| {
+        let element = seq
+            .next_element_seed(seed())
+            .map_err(|e| error = Some(A::Error::custom(format!("element {}: {}", index, e))))
+            .ok()
+            .flatten();
+        index += 1;
+        element
+    })
+    .collect();
+
+    match error {
+        Some(e) => Err(e),
+        None => Ok(collection),
+    }
+}
+
+/// Convert a decoded list into an array, rejecting lists of any length other than `N`.
+pub(crate) fn to_array<E, T, const N: usize>(values: Vec<T>) -> Result<[T; N], E>
+where
+    E: Error,
+{
+    <[T; N]>::try_from(values).map_err(|values| {
+        E::invalid_length(values.len(), &format!("a list of length {}", N).as_str())
+    })
+}
@@ -0,0 +1,432 @@
+//! `SerializeAs` and `DeserializeAs` adapters for use with `serde_with`.
+//!
+//! E.g., `#[serde_as(as = "BTreeMap<QuotedU64, HexB256>")]` on a `BTreeMap<u64, B256>`.
+//!
+//! The composable wrapper types (`HexBytes`, `HexB256`, etc.) double as marker types, and are
+//! re-exported here alongside the marker types for the encodings which have no such wrapper. Each
+//! adapter formats values exactly as its module does, including in binary formats.
+//!
+//! Markers for the `require_quotes` and `maybe_quoted` submodules are suffixed accordingly, e.g.
+//! `QuotedU64RequireQuotes` for `quoted_u64::require_quotes`.
+
+use crate::blob_hex::BYTES_PER_BLOB;
+use crate::quantity::Quantity;
+use crate::Uint256;
+use alloc::{boxed::Box, vec::Vec};
+use serde_with::{DeserializeAs, SerializeAs};
+
+pub use crate::{BoundedHexBytes, HexArray, HexBlob, HexBytes};
+#[cfg(feature = "alloy")]
+pub use crate::{ChecksummedAddress, HexAddress, HexB256};
+
+/// Implement `SerializeAs<$ty>` and `DeserializeAs<$ty>` for `$marker`, using the `serialize` and
+/// `deserialize` functions of `$module`.
+macro_rules! impl_serde_as {
+    ([$($generics: tt)*] $marker: ty, $ty: ty, $($module: ident)::+) => {
+        impl<$($generics)*> SerializeAs<$ty> for $marker {
+            fn serialize_as<S>(source: &$ty, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                $($module)::+::serialize(source, serializer)
+            }
+        }
+
+        impl<'de, $($generics)*> DeserializeAs<'de, $ty> for $marker {
+            fn deserialize_as<D>(deserializer: D) -> Result<$ty, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                $($module)::+::deserialize(deserializer)
+            }
+        }
+    };
+}
+
+impl_serde_as!([] HexBytes, Vec<u8>, crate::hex_vec);
+impl_serde_as!([const N: usize] HexArray<N>, [u8; N], crate::fixed_bytes_hex);
+impl_serde_as!([] HexBlob, Box<[u8; BYTES_PER_BLOB]>, crate::blob_hex);
+#[cfg(feature = "alloy")]
+impl_serde_as!([] HexB256, alloy_primitives::B256, crate::b256_hex);
+#[cfg(feature = "alloy")]
+impl_serde_as!([] HexAddress, alloy_primitives::Address, crate::address_hex);
+#[cfg(feature = "alloy")]
+impl_serde_as!(
+    [] ChecksummedAddress,
+    alloy_primitives::Address,
+    crate::address_hex::checksummed
+);
+
+/// Formats an `Address` with an EIP-55 checksum, and validates the checksum when deserializing, as
+/// `address_hex::strict_checksum` does.
+///
+/// Unlike `ChecksummedAddress`, mixed-case addresses are rejected unless they match their checksum.
+#[cfg(feature = "alloy")]
+pub struct StrictChecksumAddress;
+
+#[cfg(feature = "alloy")]
+impl_serde_as!(
+    [] StrictChecksumAddress,
+    alloy_primitives::Address,
+    crate::address_hex::strict_checksum
+);
+
+impl<const MAX: usize> SerializeAs<Vec<u8>> for BoundedHexBytes<MAX> {
+    fn serialize_as<S>(source: &Vec<u8>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::hex_vec::serialize(source, serializer)
+    }
+}
+
+impl<'de, const MAX: usize> DeserializeAs<'de, Vec<u8>> for BoundedHexBytes<MAX> {
+    fn deserialize_as<D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        crate::hex_vec::deserialize_bounded::<_, MAX>(deserializer)
+    }
+}
+
+macro_rules! define_quoted {
+    ($($marker: ident, $require_quotes: ident: $int: ty, $module: ident;)*) => {
+        $(
+            #[doc = concat!("Formats `", stringify!($int), "` as `", stringify!($module), "` does.")]
+            ///
+            /// Quotes are optional during decoding.
+            pub struct $marker;
+
+            impl_serde_as!([] $marker, $int, crate::$module);
+
+            #[doc = concat!(
+                "Formats `", stringify!($int), "` as `", stringify!($module), "::require_quotes` does."
+            )]
+            ///
+            /// Quotes are required during decoding.
+            pub struct $require_quotes;
+
+            impl_serde_as!([] $require_quotes, $int, crate::$module::require_quotes);
+        )*
+    };
+}
+
+define_quoted! {
+    QuotedU8, QuotedU8RequireQuotes: u8, quoted_u8;
+    QuotedU16, QuotedU16RequireQuotes: u16, quoted_u16;
+    QuotedU32, QuotedU32RequireQuotes: u32, quoted_u32;
+    QuotedU64, QuotedU64RequireQuotes: u64, quoted_u64;
+    QuotedU128, QuotedU128RequireQuotes: u128, quoted_u128;
+    QuotedUsize, QuotedUsizeRequireQuotes: usize, quoted_usize;
+    QuotedI32, QuotedI32RequireQuotes: i32, quoted_i32;
+    QuotedI64, QuotedI64RequireQuotes: i64, quoted_i64;
+    QuotedI128, QuotedI128RequireQuotes: i128, quoted_i128;
+}
+
+/// Formats the `U256` of any backend implementing `Uint256` as `quoted_u256` does.
+///
+/// Quotes are required during decoding.
+pub struct QuotedU256;
+
+impl_serde_as!([T: Uint256] QuotedU256, T, crate::quoted_u256);
+
+/// Formats the `U256` of any backend implementing `Uint256` as `quoted_u256::maybe_quoted` does.
+///
+/// Quotes are optional during decoding.
+pub struct QuotedU256MaybeQuoted;
+
+impl_serde_as!(
+    [T: Uint256] QuotedU256MaybeQuoted,
+    T,
+    crate::quoted_u256::maybe_quoted
+);
+
+/// Formats the `U256` of any backend implementing `Uint256` as a decimal string, as `u256_dec`
+/// does.
+pub struct U256Dec;
+
+impl_serde_as!([T: Uint256] U256Dec, T, crate::u256_dec);
+
+/// Formats any `Quantity` as a 0x-prefixed hex quantity, as `quantity` does.
+pub struct HexQuantity;
+
+impl_serde_as!([T: Quantity] HexQuantity, T, crate::quantity);
+
+/// Formats `u8` as a 0x-prefixed hex string of 1 byte, as `u8_hex` does.
+pub struct U8Hex;
+
+impl_serde_as!([] U8Hex, u8, crate::u8_hex);
+
+/// Formats `u32` as a 0x-prefixed, little-endian hex string, as `u32_hex` does.
+pub struct U32Hex;
+
+impl_serde_as!([] U32Hex, u32, crate::u32_hex);
+
+/// Formats `u16` as a fixed-width, 0x-prefixed, big-endian hex string, as `u16_hex_be_fixed` does.
+pub struct U16HexBeFixed;
+
+impl_serde_as!([] U16HexBeFixed, u16, crate::u16_hex_be_fixed);
+
+/// Formats `u32` as a fixed-width, 0x-prefixed, big-endian hex string, as `u32_hex_be_fixed` does.
+pub struct U32HexBeFixed;
+
+impl_serde_as!([] U32HexBeFixed, u32, crate::u32_hex_be_fixed);
+
+/// Formats `u64` as a fixed-width, 0x-prefixed, big-endian hex string, as `u64_hex_be_fixed` does.
+pub struct U64HexBeFixed;
+
+impl_serde_as!([] U64HexBeFixed, u64, crate::u64_hex_be_fixed);
+
+/// Formats `Vec<Vec<u8>>` as a list of 0x-prefixed hex strings, as `list_of_bytes_lists` does.
+pub struct ListOfBytesLists;
+
+impl_serde_as!([] ListOfBytesLists, Vec<Vec<u8>>, crate::list_of_bytes_lists);
+
+/// Formats any serializable type as a JSON-blob within a single string, as `json_str` does.
+#[cfg(feature = "json_str")]
+pub struct JsonStr;
+
+#[cfg(feature = "json_str")]
+impl_serde_as!(
+    [T: serde::Serialize + serde::de::DeserializeOwned] JsonStr,
+    T,
+    crate::json_str
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    use serde_with::serde_as;
+    use std::collections::BTreeMap;
+
+    #[serde_as]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Obj {
+        #[serde_as(as = "Vec<Option<QuotedU64>>")]
+        balances: Vec<Option<u64>>,
+        #[serde_as(as = "BTreeMap<QuotedU64RequireQuotes, HexArray<4>>")]
+        versions: BTreeMap<u64, [u8; 4]>,
+        #[serde_as(as = "Vec<QuotedI32>")]
+        deltas: Vec<i32>,
+        #[serde_as(as = "Vec<HexQuantity>")]
+        quantities: Vec<u64>,
+        #[serde_as(as = "Vec<BoundedHexBytes<4>>")]
+        data: Vec<Vec<u8>>,
+        #[serde_as(as = "ListOfBytesLists")]
+        lists: Vec<Vec<u8>>,
+        #[serde_as(as = "U8Hex")]
+        byte: u8,
+        #[serde_as(as = "U32Hex")]
+        little_endian: u32,
+        #[serde_as(as = "(U16HexBeFixed, U32HexBeFixed, U64HexBeFixed)")]
+        fixed: (u16, u32, u64),
+    }
+
+    fn obj() -> Obj {
+        Obj {
+            balances: vec![Some(32), None],
+            versions: BTreeMap::from([(1, [1, 2, 3, 4])]),
+            deltas: vec![-1, 1],
+            quantities: vec![0, 1024],
+            data: vec![vec![], vec![0xde, 0xad]],
+            lists: vec![vec![0], vec![0xbe, 0xef]],
+            byte: 1,
+            little_endian: 1,
+            fixed: (1, 1, 1),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let obj = obj();
+        let json = serde_json::to_string(&obj).unwrap();
+        assert_eq!(
+            json,
+            r#"{"balances":["32",null],"versions":{"1":"0x01020304"},"deltas":["-1","1"],"quantities":["0x0","0x400"],"data":["0x","0xdead"],"lists":["0x00","0xbeef"],"byte":"0x01","little_endian":"0x01000000","fixed":["0x0001","0x00000001","0x0000000000000001"]}"#
+        );
+        assert_eq!(serde_json::from_str::<Obj>(&json).unwrap(), obj);
+    }
+
+    #[test]
+    fn decoding() {
+        let json = serde_json::to_string(&obj()).unwrap();
+
+        // Quotes are optional for `QuotedU64`, as for `quoted_u64`.
+        let unquoted = json.replace(r#"["32",null]"#, "[32,null]");
+        assert_eq!(serde_json::from_str::<Obj>(&unquoted).unwrap(), obj());
+
+        for invalid in [
+            json.replace(r#"["-1","1"]"#, r#"["-1","1.5"]"#),
+            json.replace("0x400", "0x0400"),
+            json.replace("0xdead", "0xdeadbeef00"),
+            json.replace("0xbeef", "0xbeeg"),
+            json.replace(r#""0x01""#, r#""0x0001""#),
+            json.replace("0x0001", "0x01"),
+        ] {
+            serde_json::from_str::<Obj>(&invalid).unwrap_err();
+        }
+
+        // Quotes are required by the `require_quotes` markers.
+        #[serde_as]
+        #[derive(Debug, PartialEq, Deserialize)]
+        #[serde(transparent)]
+        struct Strict(#[serde_as(as = "Vec<QuotedU64RequireQuotes>")] Vec<u64>);
+
+        assert_eq!(
+            serde_json::from_str::<Strict>(r#"["1"]"#).unwrap(),
+            Strict(vec![1])
+        );
+        serde_json::from_str::<Strict>("[1]").unwrap_err();
+    }
+
+    #[test]
+    fn binary() {
+        let obj = obj();
+        let bytes = bincode::serialize(&obj).unwrap();
+        assert_eq!(bincode::deserialize::<Obj>(&bytes).unwrap(), obj);
+    }
+
+    #[cfg(any(feature = "alloy", feature = "ruint"))]
+    #[test]
+    fn u256() {
+        use crate::ruint::aliases::U256;
+
+        #[serde_as]
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Amounts {
+            #[serde_as(as = "Option<QuotedU256>")]
+            amount: Option<U256>,
+            #[serde_as(as = "Vec<QuotedU256MaybeQuoted>")]
+            amounts: Vec<U256>,
+            #[serde_as(as = "U256Dec")]
+            total: U256,
+        }
+
+        let amounts = Amounts {
+            amount: Some(U256::from(1024)),
+            amounts: vec![U256::from(1)],
+            total: U256::MAX,
+        };
+        let json = serde_json::to_string(&amounts).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"amount":"1024","amounts":["1"],"total":"{}"}}"#,
+                U256::MAX
+            )
+        );
+        assert_eq!(serde_json::from_str::<Amounts>(&json).unwrap(), amounts);
+
+        let unquoted = json.replace(r#"["1"]"#, "[1]");
+        assert_eq!(serde_json::from_str::<Amounts>(&unquoted).unwrap(), amounts);
+        serde_json::from_str::<Amounts>(&json.replace(r#""1024""#, "1024")).unwrap_err();
+
+        let bytes = bincode::serialize(&amounts).unwrap();
+        assert_eq!(bincode::deserialize::<Amounts>(&bytes).unwrap(), amounts);
+    }
+
+    #[cfg(feature = "alloy")]
+    #[test]
+    fn alloy() {
+        use alloy_primitives::{Address, B256};
+
+        #[serde_as]
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Alloy {
+            #[serde_as(as = "BTreeMap<QuotedU64, HexB256>")]
+            roots: BTreeMap<u64, B256>,
+            #[serde_as(as = "ChecksummedAddress")]
+            address: Address,
+            #[serde_as(as = "StrictChecksumAddress")]
+            strict: Address,
+            #[serde_as(as = "Vec<HexAddress>")]
+            addresses: Vec<Address>,
+        }
+
+        let alloy = Alloy {
+            roots: BTreeMap::from([(1, B256::repeat_byte(0xab))]),
+            address: Address::repeat_byte(0xaa),
+            strict: Address::repeat_byte(0xaa),
+            addresses: vec![Address::ZERO],
+        };
+        let checksum = Address::repeat_byte(0xaa).to_checksum(None);
+        let json = serde_json::to_string(&alloy).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"roots":{{"1":"0x{}"}},"address":"{}","strict":"{}","addresses":["0x{}"]}}"#,
+                "ab".repeat(32),
+                checksum,
+                checksum,
+                "00".repeat(20),
+            )
+        );
+        assert_eq!(serde_json::from_str::<Alloy>(&json).unwrap(), alloy);
+
+        let bad_checksum: String = checksum
+            .chars()
+            .map(|c| match c {
+                'x' => c,
+                c if c.is_ascii_lowercase() => c.to_ascii_uppercase(),
+                c => c.to_ascii_lowercase(),
+            })
+            .collect();
+        // Only the strict marker validates the checksum.
+        let address = format!(r#""address":"{}""#, checksum);
+        let bad_address = format!(r#""address":"{}""#, bad_checksum);
+        assert_eq!(
+            serde_json::from_str::<Alloy>(&json.replace(&address, &bad_address)).unwrap(),
+            alloy
+        );
+        serde_json::from_str::<Alloy>(&json.replace(&checksum, &bad_checksum)).unwrap_err();
+
+        let bytes = bincode::serialize(&alloy).unwrap();
+        assert_eq!(bincode::deserialize::<Alloy>(&bytes).unwrap(), alloy);
+    }
+
+    /// Each wrapper type accepts and rejects the same inputs as a marker type.
+    #[cfg(feature = "alloy")]
+    #[test]
+    fn checksummed_address() {
+        use alloy_primitives::Address;
+
+        #[serde_as]
+        #[derive(Debug, PartialEq, Deserialize)]
+        #[serde(transparent)]
+        struct Marker(#[serde_as(as = "ChecksummedAddress")] Address);
+
+        for json in [
+            r#""0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed""#,
+            r#""0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD""#,
+            r#""0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed""#,
+            r#""0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED""#,
+            r#""5aaeb6053f3e94c9b9a09f33669435e7ef1beaed""#,
+            r#""0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea""#,
+            r#""0x5aaeb6053f3e94c9b9a09f33669435e7ef1beazz""#,
+        ] {
+            let wrapper = serde_json::from_str::<ChecksummedAddress>(json);
+            let marker = serde_json::from_str::<Marker>(json);
+            assert_eq!(
+                wrapper.as_ref().ok().map(|wrapper| wrapper.value),
+                marker.as_ref().ok().map(|marker| marker.0),
+                "{}",
+                json
+            );
+        }
+    }
+
+    #[cfg(feature = "json_str")]
+    #[test]
+    fn json_str() {
+        #[serde_as]
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        #[serde(transparent)]
+        struct Nested(#[serde_as(as = "JsonStr")] Vec<u8>);
+
+        let nested = Nested(vec![1, 2]);
+        let json = serde_json::to_string(&nested).unwrap();
+        assert_eq!(json, r#""[1,2]""#);
+        assert_eq!(serde_json::from_str::<Nested>(&json).unwrap(), nested);
+    }
+}
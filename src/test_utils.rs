@@ -0,0 +1,42 @@
+//! Helpers shared by the tests of several modules.
+
+use core::fmt::Debug;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Define `OptionWrapper`, with a single `Option<$ty>` field formatted by the `option` submodule of
+/// the module under test, which may be omitted entirely.
+macro_rules! option_wrapper {
+    ($ty: ty) => {
+        #[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct OptionWrapper {
+            #[serde(with = "super::option", default)]
+            val: Option<$ty>,
+        }
+    };
+}
+
+pub(crate) use option_wrapper;
+
+/// Check the behaviour shared by every `option` submodule, given an `OptionWrapper` holding a
+/// value and that value's encoding.
+///
+/// `Some` is formatted as the value itself, `None` is formatted as `null`, and both `null` and a
+/// missing field decode as `None`. Each of the `invalid` encodings is rejected.
+pub fn check_option<W>(some: W, some_json: &str, invalid: &[&str])
+where
+    W: Serialize + DeserializeOwned + Default + PartialEq + Debug,
+{
+    let json = format!(r#"{{"val":{}}}"#, some_json);
+    assert_eq!(serde_json::to_string(&some).unwrap(), json);
+    assert_eq!(serde_json::from_str::<W>(&json).unwrap(), some);
+
+    let none = W::default();
+    assert_eq!(serde_json::to_string(&none).unwrap(), r#"{"val":null}"#);
+    for json in [r#"{"val":null}"#, "{}"] {
+        assert_eq!(serde_json::from_str::<W>(json).unwrap(), none);
+    }
+
+    for value in invalid {
+        serde_json::from_str::<W>(&format!(r#"{{"val":{}}}"#, value)).unwrap_err();
+    }
+}
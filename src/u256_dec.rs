@@ -1,24 +1,90 @@
-use alloy_primitives::U256;
+//! Formats a `U256` as a decimal string.
+//!
+//! E.g., `1024` serializes as `"1024"`.
+//!
+//! Works for the `U256` of any backend implementing `Uint256`.
+
+use crate::Uint256;
+use alloc::{format, string::String};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
-pub fn serialize<S>(num: &U256, serializer: S) -> Result<S::Ok, S::Error>
+pub fn serialize<S, T>(num: &T, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
+    T: Uint256,
 {
-    num.to_string().serialize(serializer)
+    if !serializer.is_human_readable() {
+        return num.serialize_binary(serializer);
+    }
+
+    serializer.collect_str(num)
 }
 
-pub fn deserialize<'de, D>(deserializer: D) -> Result<U256, D::Error>
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
 where
     D: Deserializer<'de>,
+    T: Uint256,
 {
+    if !deserializer.is_human_readable() {
+        return T::deserialize_binary(deserializer);
+    }
+
     let s = String::deserialize(deserializer)?;
-    U256::from_str_radix(&s, 10).map_err(|e| de::Error::custom(format!("Invalid U256 string: {e}")))
+    crate::uint256::parse_dec::<T>(&s)
+        .map_err(|e| de::Error::custom(format!("Invalid U256 string: {e}")))
+}
+
+/// The JSON schema of a `U256` decimal string.
+///
+/// Usage: `#[schemars(schema_with = "u256_dec::schema")]`.
+#[cfg(feature = "schemars")]
+pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    crate::schema::quoted_int(false, true)
 }
 
-#[cfg(test)]
+/// The OpenAPI schema of a `U256` decimal string.
+///
+/// Usage: `#[schema(schema_with = u256_dec::openapi_schema)]`.
+#[cfg(feature = "utoipa")]
+pub fn openapi_schema() -> utoipa::openapi::schema::Schema {
+    crate::openapi::quoted_int(false, 256)
+}
+
+/// Formats `Option<U256>` as a decimal string or `null`.
+///
+/// Usage: `#[serde(with = "u256_dec::option", default)]`.
+pub mod option {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(transparent, bound = "T: Uint256")]
+    struct Wrapper<T>(#[serde(with = "super")] T);
+
+    pub fn serialize<S, T>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Uint256,
+    {
+        value.map(Wrapper).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Uint256,
+    {
+        Ok(Option::<Wrapper<T>>::deserialize(deserializer)?.map(|wrapper| wrapper.0))
+    }
+
+    #[cfg(feature = "schemars")]
+    pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::schema::nullable(crate::schema::quoted_int(false, true))
+    }
+}
+
+#[cfg(all(test, any(feature = "alloy", feature = "ruint")))]
 mod test {
-    use alloy_primitives::U256;
+    use crate::ruint::aliases::U256;
     use serde::{Deserialize, Serialize};
     use serde_json;
 
@@ -114,4 +180,25 @@ mod test {
         serde_json::from_str::<Wrapper>("\"-1\"").unwrap_err();
         serde_json::from_str::<Wrapper>("\"ff\"").unwrap_err();
     }
+
+    crate::test_utils::option_wrapper!(U256);
+
+    #[test]
+    fn option() {
+        crate::test_utils::check_option(
+            OptionWrapper {
+                val: Some(U256::from(1024)),
+            },
+            r#""1024""#,
+            &[r#""0x400""#],
+        );
+    }
+
+    #[test]
+    fn binary() {
+        let wrapper = Wrapper { val: U256::MAX };
+        let bytes = bincode::serialize(&wrapper).unwrap();
+        assert_eq!(bytes.len(), 8 + 32);
+        assert_eq!(bincode::deserialize::<Wrapper>(&bytes).unwrap(), wrapper);
+    }
 }
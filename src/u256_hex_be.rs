@@ -1,60 +1,44 @@
-use alloy_primitives::U256;
-use serde::de::Visitor;
-use serde::{de, Deserializer, Serialize, Serializer};
-use std::fmt;
-use std::str::FromStr;
+//! Formats `U256` as a 0x-prefixed, big-endian hex quantity.
+//!
+//! E.g., `1024` serializes as `"0x400"`.
+//!
+//! Works for the `U256` of any backend implementing `Uint256`.
 
-pub fn serialize<S>(num: &U256, serializer: S) -> Result<S::Ok, S::Error>
+use crate::{quantity, Uint256};
+use serde::{Deserializer, Serializer};
+
+pub fn serialize<S, T>(num: &T, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
+    T: Uint256,
 {
-    num.serialize(serializer)
-}
-
-pub struct U256Visitor;
-
-impl<'de> Visitor<'de> for U256Visitor {
-    type Value = String;
-
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("a well formatted hex string")
-    }
-
-    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
-    where
-        E: de::Error,
-    {
-        if !value.starts_with("0x") {
-            return Err(de::Error::custom("must start with 0x"));
-        }
-        let stripped = &value[2..];
-        if stripped.is_empty() {
-            Err(de::Error::custom(format!(
-                "quantity cannot be {:?}",
-                stripped
-            )))
-        } else if stripped == "0" {
-            Ok(value.to_string())
-        } else if stripped.starts_with('0') {
-            Err(de::Error::custom("cannot have leading zero"))
-        } else {
-            Ok(value.to_string())
-        }
-    }
+    quantity::serialize(num, serializer)
 }
 
-pub fn deserialize<'de, D>(deserializer: D) -> Result<U256, D::Error>
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
 where
     D: Deserializer<'de>,
+    T: Uint256,
 {
-    let decoded = deserializer.deserialize_string(U256Visitor)?;
+    quantity::deserialize(deserializer)
+}
 
-    U256::from_str(&decoded).map_err(|e| de::Error::custom(format!("Invalid U256 string: {}", e)))
+/// The JSON schema of a `U256` quantity.
+///
+/// Usage: `#[schemars(schema_with = "u256_hex_be::schema")]`.
+#[cfg(feature = "schemars")]
+pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    crate::schema::quantity(256)
 }
 
-#[cfg(test)]
+/// Formats `Option<U256>` as a quantity or `null`.
+///
+/// Usage: `#[serde(with = "u256_hex_be::option", default)]`.
+pub use crate::quantity::option;
+
+#[cfg(all(test, any(feature = "alloy", feature = "ruint")))]
 mod test {
-    use alloy_primitives::U256;
+    use crate::ruint::aliases::U256;
     use serde::{Deserialize, Serialize};
     use serde_json;
 
@@ -147,5 +131,23 @@ mod test {
         serde_json::from_str::<Wrapper>("\"0x0400\"").unwrap_err();
         serde_json::from_str::<Wrapper>("\"400\"").unwrap_err();
         serde_json::from_str::<Wrapper>("\"ff\"").unwrap_err();
+        serde_json::from_str::<Wrapper>("\"0xfg\"").unwrap_err();
+        serde_json::from_str::<Wrapper>(
+            "\"0x1ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\"",
+        )
+        .unwrap_err();
+    }
+
+    crate::test_utils::option_wrapper!(U256);
+
+    #[test]
+    fn option() {
+        crate::test_utils::check_option(
+            OptionWrapper {
+                val: Some(U256::from(1024)),
+            },
+            r#""0x400""#,
+            &[r#""400""#],
+        );
     }
 }
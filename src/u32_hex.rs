@@ -1,25 +1,45 @@
 //! Formats `u32` as a 0x-prefixed, little-endian hex string.
 //!
 //! E.g., `0` serializes as `"0x00000000"`.
+//!
+//! Binary (non human-readable) formats store the integer natively.
+//!
+//! See `u32_hex_be_fixed` for the big-endian equivalent.
 
 use crate::bytes_4_hex;
-use serde::{Deserializer, Serializer};
+use crate::hex::serialize_prefixed;
+use serde::{Deserialize, Deserializer, Serializer};
 
 pub fn serialize<S>(num: &u32, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    let hex = format!("0x{}", hex::encode(num.to_le_bytes()));
-    serializer.serialize_str(&hex)
+    if !serializer.is_human_readable() {
+        return serializer.serialize_u32(*num);
+    }
+
+    serialize_prefixed(&num.to_le_bytes(), serializer)
 }
 
 pub fn deserialize<'de, D>(deserializer: D) -> Result<u32, D::Error>
 where
     D: Deserializer<'de>,
 {
+    if !deserializer.is_human_readable() {
+        return u32::deserialize(deserializer);
+    }
+
     bytes_4_hex::deserialize(deserializer).map(u32::from_le_bytes)
 }
 
+/// The JSON schema of a `u32` as a 0x-prefixed hex string of 4 bytes.
+///
+/// Usage: `#[schemars(schema_with = "u32_hex::schema")]`.
+#[cfg(feature = "schemars")]
+pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    crate::schema::fixed_hex_bytes(4)
+}
+
 #[cfg(test)]
 pub mod test {
     use serde::{Deserialize, Serialize};
@@ -68,4 +88,14 @@ pub mod test {
         // Requires 0x.
         serde_json::from_str::<Wrapper>("\"00000000\"").unwrap_err();
     }
+
+    #[test]
+    fn binary() {
+        let bytes = bincode::serialize(&Wrapper { val: 5 }).unwrap();
+        assert_eq!(bytes, [5, 0, 0, 0]);
+        assert_eq!(
+            bincode::deserialize::<Wrapper>(&bytes).unwrap(),
+            Wrapper { val: 5 }
+        );
+    }
 }
@@ -1,31 +1,41 @@
 //! Formats `u8` as a 0x-prefixed hex string.
 //!
 //! E.g., `0` serializes as `"0x00"`.
+//!
+//! Binary (non human-readable) formats store the integer natively.
 
-use crate::hex::PrefixedHexVisitor;
-use serde::de::Error;
-use serde::{Deserializer, Serializer};
+use crate::hex::{serialize_prefixed, PrefixedHexArrayVisitor};
+use serde::{Deserialize, Deserializer, Serializer};
 
 pub fn serialize<S>(byte: &u8, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    let hex = format!("0x{}", hex::encode([*byte]));
-    serializer.serialize_str(&hex)
+    if !serializer.is_human_readable() {
+        return serializer.serialize_u8(*byte);
+    }
+
+    serialize_prefixed(&[*byte], serializer)
 }
 
 pub fn deserialize<'de, D>(deserializer: D) -> Result<u8, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let bytes = deserializer.deserialize_str(PrefixedHexVisitor)?;
-    if bytes.len() != 1 {
-        return Err(D::Error::custom(format!(
-            "expected 1 byte for u8, got {}",
-            bytes.len()
-        )));
+    if !deserializer.is_human_readable() {
+        return u8::deserialize(deserializer);
     }
-    Ok(bytes[0])
+
+    let [byte] = deserializer.deserialize_str(PrefixedHexArrayVisitor)?;
+    Ok(byte)
+}
+
+/// The JSON schema of a `u8` as a 0x-prefixed hex string of 1 byte.
+///
+/// Usage: `#[schemars(schema_with = "u8_hex::schema")]`.
+#[cfg(feature = "schemars")]
+pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    crate::schema::fixed_hex_bytes(1)
 }
 
 #[cfg(test)]
@@ -75,4 +85,14 @@ mod test {
         // Wrong length.
         serde_json::from_str::<Wrapper>("\"0xfff\"").unwrap_err();
     }
+
+    #[test]
+    fn binary() {
+        let bytes = bincode::serialize(&Wrapper { val: 109 }).unwrap();
+        assert_eq!(bytes, [109]);
+        assert_eq!(
+            bincode::deserialize::<Wrapper>(&bytes).unwrap(),
+            Wrapper { val: 109 }
+        );
+    }
 }
@@ -0,0 +1,208 @@
+//! The 256-bit unsigned integers of each supported big integer backend.
+
+use crate::quantity::Quantity;
+use core::fmt;
+
+/// A 256-bit unsigned integer which can be formatted by `quoted_u256`, `u256_dec` and
+/// `u256_hex_be`.
+///
+/// Implemented for ruint's `U256` (also re-exported by alloy) with the `alloy` or `ruint` feature,
+/// and for primitive-types' `U256` with the `primitive-types` feature.
+pub trait Uint256: Quantity + fmt::Display {
+    /// Parse from decimal digits.
+    ///
+    /// Only called with a non-empty string of ASCII digits, see `parse_dec`.
+    fn from_dec_str(digits: &str) -> Result<Self, impl fmt::Display>;
+
+    /// Convert from a `u128`, which always fits.
+    fn from_u128(value: u128) -> Self;
+}
+
+/// An error from `parse_dec`.
+enum DecError<E> {
+    /// The string is empty, or contains something other than ASCII digits.
+    NotDigits,
+    /// The backend rejected the digits, e.g. because they overflow.
+    Backend(E),
+}
+
+impl<E: fmt::Display> fmt::Display for DecError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecError::NotDigits => write!(f, "expected decimal digits"),
+            DecError::Backend(e) => e.fmt(f),
+        }
+    }
+}
+
+/// Parse a decimal `U256` with any backend.
+///
+/// The backends disagree on inputs other than plain digits (e.g. ruint accepts `_` separators and
+/// both accept the empty string), so those are rejected here first.
+pub(crate) fn parse_dec<'a, T: Uint256 + 'a>(digits: &'a str) -> Result<T, impl fmt::Display + 'a> {
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(DecError::NotDigits);
+    }
+    T::from_dec_str(digits).map_err(DecError::Backend)
+}
+
+#[cfg(any(feature = "alloy", feature = "ruint"))]
+impl Uint256 for crate::ruint::aliases::U256 {
+    fn from_dec_str(digits: &str) -> Result<Self, impl fmt::Display> {
+        Self::from_str_radix(digits, 10)
+    }
+
+    fn from_u128(value: u128) -> Self {
+        Self::from(value)
+    }
+}
+
+#[cfg(feature = "primitive-types")]
+impl Uint256 for primitive_types::U256 {
+    fn from_dec_str(digits: &str) -> Result<Self, impl fmt::Display> {
+        Self::from_dec_str(digits)
+    }
+
+    fn from_u128(value: u128) -> Self {
+        Self::from(value)
+    }
+}
+
+#[cfg(all(
+    test,
+    any(feature = "alloy", feature = "ruint", feature = "primitive-types")
+))]
+mod test {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    /// `U256::MAX` in decimal.
+    const MAX: &str =
+        "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Obj<T: Uint256> {
+        #[serde(with = "crate::quoted_u256")]
+        quoted: T,
+        #[serde(with = "crate::quoted_u256::maybe_quoted")]
+        maybe_quoted: T,
+        #[serde(with = "crate::u256_dec")]
+        dec: T,
+        #[serde(with = "crate::u256_hex_be")]
+        hex: T,
+        #[serde(with = "crate::quoted_seq")]
+        seq: Vec<T>,
+    }
+
+    fn obj<T: Uint256>(value: T) -> Obj<T> {
+        Obj {
+            quoted: value,
+            maybe_quoted: value,
+            dec: value,
+            hex: value,
+            seq: vec![value, T::from_u128(u128::MAX)],
+        }
+    }
+
+    /// Check the encodings of a backend, given its `U256::MAX`.
+    fn check<T: Uint256 + PartialEq + fmt::Debug>(max: T) {
+        assert_eq!(max.to_string(), MAX);
+        let json = serde_json::to_string(&obj(max)).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"quoted":"{max}","maybe_quoted":"{max}","dec":"{max}","hex":"0x{}","seq":["{max}","{}"]}}"#,
+                "f".repeat(64),
+                u128::MAX,
+                max = MAX,
+            )
+        );
+        assert_eq!(serde_json::from_str::<Obj<T>>(&json).unwrap(), obj(max));
+
+        let bytes = bincode::serialize(&obj(max)).unwrap();
+        assert_eq!(bincode::deserialize::<Obj<T>>(&bytes).unwrap(), obj(max));
+
+        assert_eq!(
+            serde_json::from_str::<crate::quoted_u256::Quoted<T>>(r#""1""#)
+                .unwrap()
+                .value,
+            T::from_u128(1)
+        );
+        assert_eq!(
+            serde_json::from_str::<crate::quoted_u256::MaybeQuoted<T>>("1")
+                .unwrap()
+                .value,
+            T::from_u128(1)
+        );
+
+        for invalid in invalid() {
+            assert!(rejects::<T>(&invalid), "{}", invalid);
+        }
+    }
+
+    /// Strings which are not a decimal `U256`, including some accepted by only some backends.
+    fn invalid() -> Vec<String> {
+        let mut invalid = vec![format!(r#""1{}""#, MAX)];
+        for digits in ["12a", "", "1_0", "+1", " 1", "-0"] {
+            invalid.push(format!(r#""{}""#, digits));
+        }
+        invalid
+    }
+
+    /// Whether every decimal module rejects `json`.
+    fn rejects<T: Uint256>(json: &str) -> bool {
+        let de = || serde_json::Deserializer::from_str(json);
+        crate::quoted_u256::deserialize::<_, T>(&mut de()).is_err()
+            && crate::quoted_u256::maybe_quoted::deserialize::<_, T>(&mut de()).is_err()
+            && crate::u256_dec::deserialize::<_, T>(&mut de()).is_err()
+    }
+
+    #[cfg(any(feature = "alloy", feature = "ruint"))]
+    #[test]
+    fn ruint() {
+        check(crate::ruint::aliases::U256::MAX);
+    }
+
+    #[cfg(feature = "primitive-types")]
+    #[test]
+    fn primitive_types() {
+        check(primitive_types::U256::MAX);
+    }
+
+    /// Every backend has the same human-readable and binary encodings.
+    #[cfg(all(any(feature = "alloy", feature = "ruint"), feature = "primitive-types"))]
+    #[test]
+    fn backends_agree() {
+        use crate::ruint::aliases::U256 as RuintU256;
+        use primitive_types::U256 as PrimitiveU256;
+
+        for (ruint, primitive) in [
+            (RuintU256::ZERO, PrimitiveU256::zero()),
+            (RuintU256::from(1024), PrimitiveU256::from(1024)),
+            (RuintU256::MAX, PrimitiveU256::MAX),
+        ] {
+            let json = serde_json::to_string(&obj(ruint)).unwrap();
+            assert_eq!(serde_json::to_string(&obj(primitive)).unwrap(), json);
+            assert_eq!(
+                serde_json::from_str::<Obj<PrimitiveU256>>(&json).unwrap(),
+                obj(primitive)
+            );
+
+            let bytes = bincode::serialize(&obj(ruint)).unwrap();
+            assert_eq!(bincode::serialize(&obj(primitive)).unwrap(), bytes);
+            assert_eq!(
+                bincode::deserialize::<Obj<PrimitiveU256>>(&bytes).unwrap(),
+                obj(primitive)
+            );
+        }
+
+        for invalid in invalid() {
+            assert_eq!(
+                rejects::<RuintU256>(&invalid),
+                rejects::<PrimitiveU256>(&invalid),
+                "{}",
+                invalid
+            );
+        }
+    }
+}